@@ -1,2 +1,42 @@
 pub mod r#virtual;
-pub mod test_virtual;
\ No newline at end of file
+pub mod test_virtual;
+pub mod stack_distance;
+pub mod test_stack_distance;
+pub mod belady;
+pub mod test_belady;
+pub mod multilevel;
+pub mod test_multilevel;
+pub mod tlb;
+pub mod test_tlb;
+pub mod page_map;
+pub mod test_page_map;
+pub mod address;
+pub mod test_address;
+pub mod pager;
+pub mod test_pager;
+pub mod pff;
+pub mod test_pff;
+pub mod cost;
+pub mod test_cost;
+pub mod prefetch;
+pub mod test_prefetch;
+pub mod cow;
+pub mod test_cow;
+pub mod mmap;
+pub mod test_mmap;
+pub mod swap;
+pub mod test_swap;
+pub mod huge;
+pub mod test_huge;
+pub mod checkpoint;
+pub mod test_checkpoint;
+pub mod trace;
+pub mod test_trace;
+pub mod workload;
+pub mod test_workload;
+pub mod events;
+pub mod test_events;
+pub mod classify;
+pub mod test_classify;
+pub mod compare;
+pub mod test_compare;
@@ -0,0 +1,150 @@
+use crate::r#virtual::r#virtual::{first_timestamp, PolicyState, ReplacementPolicy, PTE};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AccessOutcome {
+    Hit {
+        page_number: usize,
+        physical_address: u64,
+    },
+    Fault {
+        page_number: usize,
+        physical_address: u64,
+        evicted_page: Option<usize>,
+    },
+    OutOfRange {
+        virtual_address: u64,
+    },
+    NoFrame {
+        page_number: usize,
+    },
+}
+
+impl AccessOutcome {
+    pub fn physical_address(&self) -> Option<u64> {
+        match *self {
+            AccessOutcome::Hit {
+                physical_address, ..
+            }
+            | AccessOutcome::Fault {
+                physical_address, ..
+            } => Some(physical_address),
+            _ => None,
+        }
+    }
+}
+
+// Splits raw virtual byte addresses into page number and offset and drives the
+// page table through the chosen replacement policy, one address at a time.
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
+pub struct AddressTranslator {
+    pub page_size: u64,
+    pub page_table: Vec<PTE>,
+    pub frame_pool: Vec<i32>,
+    pub policy: ReplacementPolicy,
+    pub policy_state: PolicyState,
+    pub current_timestamp: i32,
+    pub hits: usize,
+    pub faults: usize,
+    frame_owner: HashMap<i32, usize>,
+}
+
+impl AddressTranslator {
+    pub fn new(
+        page_size: u64,
+        page_count: usize,
+        frame_count: usize,
+        policy: ReplacementPolicy,
+    ) -> Self {
+        let frame_pool: Vec<i32> = (0..frame_count as i32).rev().collect();
+        AddressTranslator::from_parts(
+            page_size,
+            vec![PTE::default(); page_count],
+            frame_pool,
+            policy,
+        )
+    }
+
+    pub fn from_parts(
+        page_size: u64,
+        page_table: Vec<PTE>,
+        frame_pool: Vec<i32>,
+        policy: ReplacementPolicy,
+    ) -> Self {
+        assert!(page_size > 0, "page size must be at least one byte");
+        let frame_owner = page_table
+            .iter()
+            .enumerate()
+            .filter(|(_, pte)| pte.is_valid)
+            .map(|(page_number, pte)| (pte.frame_number, page_number))
+            .collect();
+        AddressTranslator {
+            page_size,
+            policy_state: PolicyState::new(policy, &page_table, &frame_pool),
+            current_timestamp: first_timestamp(&page_table),
+            page_table,
+            frame_pool,
+            policy,
+            hits: 0,
+            faults: 0,
+            frame_owner,
+        }
+    }
+
+    pub fn split(&self, virtual_address: u64) -> (usize, u64) {
+        (
+            (virtual_address / self.page_size) as usize,
+            virtual_address % self.page_size,
+        )
+    }
+
+    pub fn access(&mut self, virtual_address: u64) -> AccessOutcome {
+        let (page_number, offset) = self.split(virtual_address);
+        if page_number >= self.page_table.len() {
+            return AccessOutcome::OutOfRange { virtual_address };
+        }
+
+        let was_resident = self.page_table[page_number].is_valid;
+        let frame = self.policy_state.process_page_access(
+            &mut self.page_table,
+            page_number,
+            &mut self.frame_pool,
+            self.current_timestamp,
+        );
+        self.current_timestamp += 1;
+        if frame < 0 {
+            return AccessOutcome::NoFrame { page_number };
+        }
+
+        let physical_address = frame as u64 * self.page_size + offset;
+        if was_resident {
+            self.hits += 1;
+            return AccessOutcome::Hit {
+                page_number,
+                physical_address,
+            };
+        }
+
+        self.faults += 1;
+        let evicted_page = self
+            .frame_owner
+            .insert(frame, page_number)
+            .filter(|&previous| previous != page_number);
+        AccessOutcome::Fault {
+            page_number,
+            physical_address,
+            evicted_page,
+        }
+    }
+
+    pub fn access_all<I>(&mut self, virtual_addresses: I) -> Vec<AccessOutcome>
+    where
+        I: IntoIterator<Item = u64>,
+    {
+        virtual_addresses
+            .into_iter()
+            .map(|virtual_address| self.access(virtual_address))
+            .collect()
+    }
+}
@@ -0,0 +1,100 @@
+use crate::r#virtual::r#virtual::{ReplacementPolicy, SeededRng};
+use serde::{Deserialize, Serialize};
+
+// Adding a frame (`frames` -> `frames + 1`) increased the fault count.
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BeladyAnomaly {
+    pub frames: usize,
+    pub faults: i32,
+    pub next_faults: i32,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
+pub struct BeladyExample {
+    pub reference_string: Vec<i32>,
+    pub anomaly: BeladyAnomaly,
+}
+
+pub fn sweep_frame_counts(
+    policy: ReplacementPolicy,
+    page_references: &[i32],
+    min_frames: usize,
+    max_frames: usize,
+) -> Vec<(usize, i32)> {
+    (min_frames..=max_frames)
+        .map(|frames| {
+            (
+                frames,
+                policy.count_page_faults_for_frames(page_references, frames),
+            )
+        })
+        .collect()
+}
+
+pub fn detect_belady_anomalies(
+    policy: ReplacementPolicy,
+    page_references: &[i32],
+    min_frames: usize,
+    max_frames: usize,
+) -> Vec<BeladyAnomaly> {
+    let sweep = sweep_frame_counts(policy, page_references, min_frames, max_frames);
+    sweep
+        .windows(2)
+        .filter(|pair| pair[1].1 > pair[0].1)
+        .map(|pair| BeladyAnomaly {
+            frames: pair[0].0,
+            faults: pair[0].1,
+            next_faults: pair[1].1,
+        })
+        .collect()
+}
+
+fn first_fifo_anomaly(reference_string: &[i32], max_frames: usize) -> Option<BeladyAnomaly> {
+    detect_belady_anomalies(ReplacementPolicy::Fifo, reference_string, 1, max_frames)
+        .first()
+        .copied()
+}
+
+// Randomly samples reference strings over `page_count` pages until one makes
+// FIFO fault more with more frames, trying at most `attempts` strings. The hit
+// is then shortened by dropping references for as long as the anomaly remains.
+pub fn find_fifo_belady_anomaly(
+    page_count: usize,
+    length: usize,
+    max_frames: usize,
+    seed: u64,
+    attempts: usize,
+) -> Option<BeladyExample> {
+    if page_count == 0 {
+        return None;
+    }
+    let mut rng = SeededRng::new(seed);
+    for _attempt in 0..attempts {
+        let mut reference_string: Vec<i32> = (0..length)
+            .map(|_| rng.next_below(page_count) as i32)
+            .collect();
+        let mut anomaly = match first_fifo_anomaly(&reference_string, max_frames) {
+            Some(anomaly) => anomaly,
+            None => continue,
+        };
+
+        let mut position = 0;
+        while position < reference_string.len() {
+            let mut shorter = reference_string.clone();
+            shorter.remove(position);
+            match first_fifo_anomaly(&shorter, max_frames) {
+                Some(shorter_anomaly) => {
+                    reference_string = shorter;
+                    anomaly = shorter_anomaly;
+                }
+                None => position += 1,
+            }
+        }
+
+        return Some(BeladyExample {
+            reference_string,
+            anomaly,
+        });
+    }
+    None
+}
@@ -0,0 +1,97 @@
+use crate::r#virtual::address::AddressTranslator;
+use crate::r#virtual::r#virtual::PTE;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::io;
+use std::path::Path;
+
+// The full pager state, page table, frame pool, clock and policy state, is an
+// `AddressTranslator`; a checkpoint is that state as JSON. Resuming from it
+// continues the run exactly where it stopped.
+pub fn checkpoint_to_json(translator: &AddressTranslator) -> serde_json::Result<String> {
+    serde_json::to_string(translator)
+}
+
+pub fn resume_from_json(json: &str) -> serde_json::Result<AddressTranslator> {
+    serde_json::from_str(json)
+}
+
+pub fn save_checkpoint(translator: &AddressTranslator, path: &Path) -> io::Result<()> {
+    let json = serde_json::to_string_pretty(translator)?;
+    fs::write(path, json)
+}
+
+pub fn load_checkpoint(path: &Path) -> io::Result<AddressTranslator> {
+    let json = fs::read_to_string(path)?;
+    Ok(resume_from_json(&json)?)
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PteChange {
+    pub page_number: usize,
+    pub before: PTE,
+    pub after: PTE,
+}
+
+impl PteChange {
+    pub fn mapped(&self) -> bool {
+        !self.before.is_valid && self.after.is_valid
+    }
+
+    pub fn unmapped(&self) -> bool {
+        self.before.is_valid && !self.after.is_valid
+    }
+}
+
+#[derive(Deserialize, Serialize, Debug, Default, Clone, PartialEq, Eq)]
+pub struct PagerDiff {
+    pub page_changes: Vec<PteChange>,
+    pub frames_taken: Vec<i32>,
+    pub frames_returned: Vec<i32>,
+    pub timestamp_delta: i32,
+    pub hits_delta: i64,
+    pub faults_delta: i64,
+    pub policy_state_changed: bool,
+}
+
+impl PagerDiff {
+    pub fn is_empty(&self) -> bool {
+        *self == PagerDiff::default()
+    }
+}
+
+// What changed between two checkpoints of the same run. Pages whose entry
+// differs in any field are listed; the page tables must have the same size.
+pub fn diff_pagers(before: &AddressTranslator, after: &AddressTranslator) -> PagerDiff {
+    let page_changes = before
+        .page_table
+        .iter()
+        .zip(after.page_table.iter())
+        .enumerate()
+        .filter(|(_, (b, a))| b != a)
+        .map(|(page_number, (b, a))| PteChange {
+            page_number,
+            before: *b,
+            after: *a,
+        })
+        .collect();
+    PagerDiff {
+        page_changes,
+        frames_taken: before
+            .frame_pool
+            .iter()
+            .filter(|frame| !after.frame_pool.contains(frame))
+            .copied()
+            .collect(),
+        frames_returned: after
+            .frame_pool
+            .iter()
+            .filter(|frame| !before.frame_pool.contains(frame))
+            .copied()
+            .collect(),
+        timestamp_delta: after.current_timestamp - before.current_timestamp,
+        hits_delta: after.hits as i64 - before.hits as i64,
+        faults_delta: after.faults as i64 - before.faults as i64,
+        policy_state_changed: before.policy_state != after.policy_state,
+    }
+}
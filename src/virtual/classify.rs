@@ -0,0 +1,110 @@
+use crate::r#virtual::events::fault_events;
+use crate::r#virtual::r#virtual::{process_references_opt, ReplacementPolicy, PTE};
+use crate::r#virtual::stack_distance::stack_distances;
+use serde::{Deserialize, Serialize};
+
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FaultClass {
+    // First reference to the page; no policy or memory size avoids it.
+    Compulsory,
+    // A fully associative LRU memory of the same size misses too: the stack
+    // distance is greater than the frame count.
+    Capacity,
+    // The LRU shadow would have hit, and so would OPT, which never misses on a
+    // stack distance within the frame count. The miss comes from the policy's
+    // choice of victim; this is the paging analogue of a conflict miss.
+    PolicyInduced,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ClassifiedFault {
+    pub index: usize,
+    pub page_number: i32,
+    pub class: FaultClass,
+    // Whether Belady's optimal policy with the same frames also faults here.
+    pub opt_faults: bool,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FaultBreakdown {
+    pub policy: ReplacementPolicy,
+    pub frames: usize,
+    pub references: usize,
+    pub faults: usize,
+    pub compulsory: usize,
+    pub capacity: usize,
+    pub policy_induced: usize,
+    pub opt_faults: usize,
+    // Faults of the policy at references where OPT hits: every policy-induced
+    // fault plus the capacity faults OPT still avoids.
+    pub avoidable_by_opt: usize,
+}
+
+impl FaultBreakdown {
+    pub fn excess_over_opt(&self) -> i64 {
+        self.faults as i64 - self.opt_faults as i64
+    }
+}
+
+// Runs the policy on `frames` empty frames and labels every fault.
+pub fn classify_faults(
+    policy: ReplacementPolicy,
+    page_references: &[i32],
+    frames: usize,
+) -> (Vec<ClassifiedFault>, FaultBreakdown) {
+    let page_count = page_references
+        .iter()
+        .map(|&page| page as usize + 1)
+        .max()
+        .unwrap_or(0);
+    let frame_pool: Vec<i32> = (0..frames as i32).rev().collect();
+    let distances = stack_distances(page_references);
+    let opt = process_references_opt(
+        &mut vec![PTE::default(); page_count],
+        page_references,
+        &mut frame_pool.clone(),
+    );
+
+    let mut breakdown = FaultBreakdown {
+        policy,
+        frames,
+        references: page_references.len(),
+        faults: 0,
+        compulsory: 0,
+        capacity: 0,
+        policy_induced: 0,
+        opt_faults: opt.iter().filter(|&&fault| fault).count(),
+        avoidable_by_opt: 0,
+    };
+    let mut faults = Vec::new();
+    let events = fault_events(
+        policy,
+        vec![PTE::default(); page_count],
+        frame_pool,
+        page_references.iter().copied(),
+    );
+    for event in events.filter(|event| event.is_fault()) {
+        let class = match distances[event.index] {
+            None => FaultClass::Compulsory,
+            Some(distance) if distance > frames => FaultClass::Capacity,
+            Some(_) if !opt[event.index] => FaultClass::PolicyInduced,
+            Some(_) => unreachable!("OPT hits wherever the LRU shadow does"),
+        };
+        breakdown.faults += 1;
+        match class {
+            FaultClass::Compulsory => breakdown.compulsory += 1,
+            FaultClass::Capacity => breakdown.capacity += 1,
+            FaultClass::PolicyInduced => breakdown.policy_induced += 1,
+        }
+        if !opt[event.index] {
+            breakdown.avoidable_by_opt += 1;
+        }
+        faults.push(ClassifiedFault {
+            index: event.index,
+            page_number: event.page_number,
+            class,
+            opt_faults: opt[event.index],
+        });
+    }
+    (faults, breakdown)
+}
@@ -0,0 +1,170 @@
+use crate::r#virtual::r#virtual::ReplacementPolicy;
+use serde::{Deserialize, Serialize};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+use std::thread;
+
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
+pub struct NamedTrace {
+    pub name: String,
+    pub page_references: Vec<i32>,
+}
+
+impl NamedTrace {
+    pub fn new(name: &str, page_references: Vec<i32>) -> Self {
+        NamedTrace {
+            name: name.to_string(),
+            page_references,
+        }
+    }
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
+pub struct ComparisonRow {
+    pub trace: String,
+    pub policy: ReplacementPolicy,
+    pub frames: usize,
+    pub references: usize,
+    pub faults: i32,
+    pub fault_rate: f64,
+}
+
+// Rows are ordered by trace, then policy, then frame count, in the order
+// they were given, whatever order the worker threads finished in.
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
+pub struct ComparisonTable {
+    pub frame_counts: Vec<usize>,
+    pub rows: Vec<ComparisonRow>,
+}
+
+// Runs every (trace, policy, frame count) combination on empty frames, spread
+// over `threads` worker threads.
+pub fn run_comparison(
+    traces: &[NamedTrace],
+    policies: &[ReplacementPolicy],
+    frame_counts: &[usize],
+    threads: usize,
+) -> ComparisonTable {
+    let mut jobs = Vec::new();
+    for trace in traces {
+        for &policy in policies {
+            for &frames in frame_counts {
+                jobs.push((trace, policy, frames));
+            }
+        }
+    }
+    let results: Mutex<Vec<Option<ComparisonRow>>> = Mutex::new(vec![None; jobs.len()]);
+    let next_job = AtomicUsize::new(0);
+
+    thread::scope(|scope| {
+        for _ in 0..std::cmp::max(threads, 1) {
+            scope.spawn(|| loop {
+                let index = next_job.fetch_add(1, Ordering::Relaxed);
+                let (trace, policy, frames) = match jobs.get(index) {
+                    Some(&job) => job,
+                    None => break,
+                };
+                let faults = policy.count_page_faults_for_frames(&trace.page_references, frames);
+                let references = trace.page_references.len();
+                let row = ComparisonRow {
+                    trace: trace.name.clone(),
+                    policy,
+                    frames,
+                    references,
+                    faults,
+                    fault_rate: if references == 0 {
+                        0.0
+                    } else {
+                        faults as f64 / references as f64
+                    },
+                };
+                results.lock().unwrap()[index] = Some(row);
+            });
+        }
+    });
+
+    ComparisonTable {
+        frame_counts: frame_counts.to_vec(),
+        rows: results
+            .into_inner()
+            .unwrap()
+            .into_iter()
+            .flatten()
+            .collect(),
+    }
+}
+
+// `name()` plus any seed or configuration, so two parameterisations of the
+// same policy get distinct rows.
+pub fn policy_label(policy: ReplacementPolicy) -> String {
+    match policy {
+        ReplacementPolicy::Random(seed) => format!("Random(seed={})", seed),
+        ReplacementPolicy::Aging(config) => format!(
+            "Aging(bits={}, tick={})",
+            config.counter_bits, config.tick_interval
+        ),
+        _ => policy.name().to_string(),
+    }
+}
+
+fn csv_field(text: &str) -> String {
+    if text.contains(',') || text.contains('"') || text.contains('\n') {
+        format!("\"{}\"", text.replace('"', "\"\""))
+    } else {
+        text.to_string()
+    }
+}
+
+impl ComparisonTable {
+    pub fn faults(&self, trace: &str, policy: ReplacementPolicy, frames: usize) -> Option<i32> {
+        self.rows
+            .iter()
+            .find(|row| row.trace == trace && row.policy == policy && row.frames == frames)
+            .map(|row| row.faults)
+    }
+
+    // One line per trace and policy, one fault-count column per frame count.
+    pub fn to_markdown(&self) -> String {
+        let mut markdown = String::from("| Trace | Policy |");
+        for frames in &self.frame_counts {
+            markdown.push_str(&format!(" {} frames |", frames));
+        }
+        markdown.push_str("\n|---|---|");
+        for _ in &self.frame_counts {
+            markdown.push_str("---:|");
+        }
+        markdown.push('\n');
+        for line in self.rows.chunks(std::cmp::max(self.frame_counts.len(), 1)) {
+            markdown.push_str(&format!(
+                "| {} | {} |",
+                line[0].trace.replace('|', "\\|"),
+                policy_label(line[0].policy)
+            ));
+            for row in line {
+                markdown.push_str(&format!(" {} |", row.faults));
+            }
+            markdown.push('\n');
+        }
+        markdown
+    }
+
+    pub fn to_csv(&self) -> String {
+        let mut csv = String::from("trace,policy,frames,references,faults,fault_rate\n");
+        for row in &self.rows {
+            csv.push_str(&format!(
+                "{},{},{},{},{},{}\n",
+                csv_field(&row.trace),
+                csv_field(&policy_label(row.policy)),
+                row.frames,
+                row.references,
+                row.faults,
+                row.fault_rate
+            ));
+        }
+        csv
+    }
+
+    pub fn to_json(&self) -> serde_json::Result<String> {
+        serde_json::to_string(self)
+    }
+}
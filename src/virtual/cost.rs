@@ -0,0 +1,200 @@
+use crate::r#virtual::address::{AccessOutcome, AddressTranslator};
+use crate::r#virtual::r#virtual::ReplacementPolicy;
+use serde::{Deserialize, Serialize};
+
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MemoryAccess {
+    pub page_number: usize,
+    pub is_write: bool,
+}
+
+impl MemoryAccess {
+    pub fn read(page_number: usize) -> Self {
+        MemoryAccess {
+            page_number,
+            is_write: false,
+        }
+    }
+
+    pub fn write(page_number: usize) -> Self {
+        MemoryAccess {
+            page_number,
+            is_write: true,
+        }
+    }
+}
+
+// Latencies in nanoseconds. Without a disk queue a dirty victim is written
+// back synchronously before the faulting page is read in. With one, the read
+// goes first and the write-back is queued behind it, overlapping with later
+// accesses; a fault that finds the disk still busy waits for it.
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq)]
+pub struct CostModel {
+    pub memory_access_ns: f64,
+    pub fault_service_ns: f64,
+    pub write_back_ns: f64,
+    pub disk_queue: bool,
+}
+
+impl Default for CostModel {
+    fn default() -> Self {
+        CostModel {
+            memory_access_ns: 100.0,
+            fault_service_ns: 8_000_000.0,
+            write_back_ns: 8_000_000.0,
+            disk_queue: false,
+        }
+    }
+}
+
+impl CostModel {
+    // Textbook EAT: a fault costs the page-in, a write-back when the victim
+    // is dirty, and the access itself once the page is resident.
+    pub fn effective_access_time(&self, fault_rate: f64, dirty_victim_ratio: f64) -> f64 {
+        let fault_time =
+            self.fault_service_ns + dirty_victim_ratio * self.write_back_ns + self.memory_access_ns;
+        (1.0 - fault_rate) * self.memory_access_ns + fault_rate * fault_time
+    }
+}
+
+#[derive(Deserialize, Serialize, Debug, Default, Clone, Copy, PartialEq)]
+pub struct CostReport {
+    pub accesses: usize,
+    pub hits: usize,
+    pub faults: usize,
+    pub write_backs: usize,
+    pub memory_time_ns: f64,
+    pub fault_service_time_ns: f64,
+    pub write_back_time_ns: f64,
+    pub queue_wait_ns: f64,
+    pub total_time_ns: f64,
+}
+
+impl CostReport {
+    pub fn fault_rate(&self) -> f64 {
+        if self.accesses == 0 {
+            0.0
+        } else {
+            self.faults as f64 / self.accesses as f64
+        }
+    }
+
+    pub fn effective_access_time(&self) -> f64 {
+        if self.accesses == 0 {
+            0.0
+        } else {
+            self.total_time_ns / self.accesses as f64
+        }
+    }
+}
+
+// Replays accesses through an `AddressTranslator` with a page size of one, so
+// virtual addresses are page numbers, and charges simulated time for each.
+#[derive(Debug, Clone)]
+pub struct DemandPagingSimulator {
+    pub model: CostModel,
+    pub translator: AddressTranslator,
+    pub dirty: Vec<bool>,
+    pub report: CostReport,
+    disk_free_at_ns: f64,
+}
+
+impl DemandPagingSimulator {
+    pub fn new(
+        model: CostModel,
+        page_count: usize,
+        frame_count: usize,
+        policy: ReplacementPolicy,
+    ) -> Self {
+        DemandPagingSimulator {
+            model,
+            translator: AddressTranslator::new(1, page_count, frame_count, policy),
+            dirty: vec![false; page_count],
+            report: CostReport::default(),
+            disk_free_at_ns: 0.0,
+        }
+    }
+
+    pub fn access(&mut self, access: MemoryAccess) -> AccessOutcome {
+        let outcome = self.translator.access(access.page_number as u64);
+        match outcome {
+            AccessOutcome::Hit { .. } => self.report.hits += 1,
+            AccessOutcome::Fault { evicted_page, .. } => {
+                self.report.faults += 1;
+                let dirty_victim = match evicted_page {
+                    Some(victim) => std::mem::replace(&mut self.dirty[victim], false),
+                    None => false,
+                };
+                if dirty_victim && !self.model.disk_queue {
+                    self.write_back();
+                }
+                let start = self.report.total_time_ns.max(self.disk_free_at_ns);
+                self.report.queue_wait_ns += start - self.report.total_time_ns;
+                self.report.total_time_ns = start + self.model.fault_service_ns;
+                self.report.fault_service_time_ns += self.model.fault_service_ns;
+                self.disk_free_at_ns = self.report.total_time_ns;
+                if dirty_victim && self.model.disk_queue {
+                    self.write_back();
+                }
+            }
+            AccessOutcome::OutOfRange { .. } | AccessOutcome::NoFrame { .. } => return outcome,
+        }
+        self.report.accesses += 1;
+        self.report.memory_time_ns += self.model.memory_access_ns;
+        self.report.total_time_ns += self.model.memory_access_ns;
+        if access.is_write {
+            self.dirty[access.page_number] = true;
+        }
+        outcome
+    }
+
+    fn write_back(&mut self) {
+        self.report.write_backs += 1;
+        self.report.write_back_time_ns += self.model.write_back_ns;
+        if self.model.disk_queue {
+            self.disk_free_at_ns += self.model.write_back_ns;
+        } else {
+            self.report.total_time_ns += self.model.write_back_ns;
+            self.disk_free_at_ns = self.report.total_time_ns;
+        }
+    }
+
+    pub fn run(&mut self, accesses: &[MemoryAccess]) -> CostReport {
+        for &access in accesses {
+            self.access(access);
+        }
+        self.report
+    }
+}
+
+pub fn simulate_cost(
+    policy: ReplacementPolicy,
+    page_count: usize,
+    frame_count: usize,
+    accesses: &[MemoryAccess],
+    model: CostModel,
+) -> CostReport {
+    DemandPagingSimulator::new(model, page_count, frame_count, policy).run(accesses)
+}
+
+// Cost of a read-only reference string, the same input `count_page_faults_*`
+// takes.
+pub fn simulate_cost_for_references(
+    policy: ReplacementPolicy,
+    page_references: &[i32],
+    frame_count: usize,
+    model: CostModel,
+) -> CostReport {
+    let accesses: Vec<MemoryAccess> = page_references
+        .iter()
+        .map(|&page| MemoryAccess::read(page as usize))
+        .collect();
+    let page_count = page_references.iter().map(|&page| page as usize + 1).max();
+    simulate_cost(
+        policy,
+        page_count.unwrap_or(0),
+        frame_count,
+        &accesses,
+        model,
+    )
+}
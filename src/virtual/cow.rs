@@ -0,0 +1,198 @@
+use crate::r#virtual::r#virtual::PTE;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+#[derive(Deserialize, Serialize, Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct CowStats {
+    pub forks: usize,
+    pub pages_shared: usize,
+    pub cow_faults: usize,
+    pub copies: usize,
+    pub protection_faults: usize,
+}
+
+impl CowStats {
+    // An eager fork copies every writable page it shares; COW only copies
+    // the ones that are written while still shared.
+    pub fn copies_avoided(&self) -> usize {
+        self.pages_shared.saturating_sub(self.copies)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WriteOutcome {
+    Written { frame: i32 },
+    Copied { old_frame: i32, new_frame: i32 },
+    ProtectionFault,
+    NotMapped,
+    OutOfMemory,
+    UnknownProcess,
+}
+
+// Physical memory shared by several processes. Every frame carries a
+// reference count of the page table entries pointing at it; a frame goes back
+// to the pool only when that count drops to zero. All mapped pages stay
+// resident, there is no replacement here.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CowMemory {
+    pub frame_refcounts: Vec<u32>,
+    pub frame_pool: Vec<i32>,
+    pub page_tables: HashMap<i32, Vec<PTE>>,
+    pub stats: CowStats,
+    pub current_timestamp: i32,
+}
+
+impl CowMemory {
+    pub fn new(frame_count: usize) -> Self {
+        CowMemory {
+            frame_refcounts: vec![0; frame_count],
+            frame_pool: (0..frame_count as i32).rev().collect(),
+            page_tables: HashMap::new(),
+            stats: CowStats::default(),
+            current_timestamp: 1,
+        }
+    }
+
+    pub fn create_process(&mut self, pid: i32, page_count: usize) -> bool {
+        if self.page_tables.contains_key(&pid) {
+            return false;
+        }
+        self.page_tables
+            .insert(pid, vec![PTE::default(); page_count]);
+        true
+    }
+
+    pub fn refcount(&self, frame: i32) -> u32 {
+        self.frame_refcounts[frame as usize]
+    }
+
+    pub fn shared_frames(&self) -> usize {
+        self.frame_refcounts
+            .iter()
+            .filter(|&&count| count > 1)
+            .count()
+    }
+
+    fn next_timestamp(&mut self) -> i32 {
+        let current_timestamp = self.current_timestamp;
+        self.current_timestamp += 1;
+        current_timestamp
+    }
+
+    // Backs a page with a fresh private frame.
+    pub fn map_page(&mut self, pid: i32, page_number: usize, read_only: bool) -> Option<i32> {
+        let current_timestamp = self.next_timestamp();
+        let page_table = self.page_tables.get_mut(&pid)?;
+        if page_number >= page_table.len() || page_table[page_number].is_valid {
+            return None;
+        }
+        let frame = self.frame_pool.pop()?;
+        self.frame_refcounts[frame as usize] = 1;
+        page_table[page_number] = PTE {
+            is_valid: true,
+            frame_number: frame,
+            arrival_timestamp: current_timestamp,
+            last_access_timestamp: current_timestamp,
+            reference_count: 1,
+            is_read_only: read_only,
+            is_cow: false,
+            is_dirty: false,
+        };
+        Some(frame)
+    }
+
+    // Gives the child a copy of the parent's page table. Every resident page
+    // shares its frame; writable pages become COW in both processes while
+    // read-only pages are simply shared.
+    pub fn fork(&mut self, parent: i32, child: i32) -> bool {
+        if self.page_tables.contains_key(&child) {
+            return false;
+        }
+        let parent_table = match self.page_tables.get_mut(&parent) {
+            Some(page_table) => page_table,
+            None => return false,
+        };
+        for pte in parent_table.iter_mut().filter(|pte| pte.is_valid) {
+            self.frame_refcounts[pte.frame_number as usize] += 1;
+            if !pte.is_read_only {
+                pte.is_cow = true;
+                self.stats.pages_shared += 1;
+            }
+        }
+        let child_table = parent_table.clone();
+        self.page_tables.insert(child, child_table);
+        self.stats.forks += 1;
+        true
+    }
+
+    pub fn read(&mut self, pid: i32, page_number: usize) -> Option<i32> {
+        let current_timestamp = self.next_timestamp();
+        let pte = self.page_tables.get_mut(&pid)?.get_mut(page_number)?;
+        if !pte.is_valid {
+            return None;
+        }
+        pte.last_access_timestamp = current_timestamp;
+        pte.reference_count += 1;
+        Some(pte.frame_number)
+    }
+
+    // A write to a COW page copies the frame unless this process is the last
+    // one referencing it, in which case the page just becomes writable again.
+    pub fn write(&mut self, pid: i32, page_number: usize) -> WriteOutcome {
+        let current_timestamp = self.next_timestamp();
+        let page_table = match self.page_tables.get_mut(&pid) {
+            Some(page_table) => page_table,
+            None => return WriteOutcome::UnknownProcess,
+        };
+        let pte = match page_table.get_mut(page_number) {
+            Some(pte) if pte.is_valid => pte,
+            _ => return WriteOutcome::NotMapped,
+        };
+        if pte.is_read_only {
+            self.stats.protection_faults += 1;
+            return WriteOutcome::ProtectionFault;
+        }
+        pte.last_access_timestamp = current_timestamp;
+        pte.reference_count += 1;
+        if !pte.is_cow {
+            return WriteOutcome::Written {
+                frame: pte.frame_number,
+            };
+        }
+
+        self.stats.cow_faults += 1;
+        let old_frame = pte.frame_number;
+        if self.frame_refcounts[old_frame as usize] == 1 {
+            pte.is_cow = false;
+            return WriteOutcome::Written { frame: old_frame };
+        }
+        let new_frame = match self.frame_pool.pop() {
+            Some(frame) => frame,
+            None => return WriteOutcome::OutOfMemory,
+        };
+        self.frame_refcounts[old_frame as usize] -= 1;
+        self.frame_refcounts[new_frame as usize] = 1;
+        pte.frame_number = new_frame;
+        pte.is_cow = false;
+        self.stats.copies += 1;
+        WriteOutcome::Copied {
+            old_frame,
+            new_frame,
+        }
+    }
+
+    pub fn exit(&mut self, pid: i32) -> bool {
+        let page_table = match self.page_tables.remove(&pid) {
+            Some(page_table) => page_table,
+            None => return false,
+        };
+        for pte in page_table.iter().filter(|pte| pte.is_valid) {
+            let count = &mut self.frame_refcounts[pte.frame_number as usize];
+            *count -= 1;
+            if *count == 0 {
+                self.frame_pool.push(pte.frame_number);
+            }
+        }
+        true
+    }
+}
@@ -0,0 +1,204 @@
+use crate::r#virtual::address::{AccessOutcome, AddressTranslator};
+use crate::r#virtual::r#virtual::{ReplacementPolicy, PTE};
+use serde::{Deserialize, Serialize};
+
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EventKind {
+    Hit,
+    // First reference to the page.
+    ColdMiss,
+    // The page was resident before and has since been evicted.
+    CapacityMiss,
+    NoFrame,
+    OutOfRange,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PageEvent {
+    pub index: usize,
+    pub page_number: i32,
+    pub kind: EventKind,
+    pub frame: i32,
+    pub evicted_page: Option<usize>,
+}
+
+impl PageEvent {
+    pub fn is_fault(&self) -> bool {
+        matches!(
+            self.kind,
+            EventKind::ColdMiss | EventKind::CapacityMiss | EventKind::NoFrame
+        )
+    }
+}
+
+// Lazily replays references through a replacement policy, yielding one event
+// per reference. Nothing is buffered, so the references may come from an
+// unbounded source; only the page table has to be sized up front.
+pub struct FaultEvents<I> {
+    references: I,
+    translator: AddressTranslator,
+    seen: Vec<bool>,
+    index: usize,
+}
+
+impl<I> FaultEvents<I> {
+    pub fn translator(&self) -> &AddressTranslator {
+        &self.translator
+    }
+}
+
+impl<I: Iterator<Item = i32>> Iterator for FaultEvents<I> {
+    type Item = PageEvent;
+
+    fn next(&mut self) -> Option<PageEvent> {
+        let page_number = self.references.next()?;
+        let index = self.index;
+        self.index += 1;
+        let mut event = PageEvent {
+            index,
+            page_number,
+            kind: EventKind::OutOfRange,
+            frame: -1,
+            evicted_page: None,
+        };
+        if page_number < 0 {
+            return Some(event);
+        }
+        let page_size = self.translator.page_size;
+        match self.translator.access(page_number as u64 * page_size) {
+            AccessOutcome::Hit {
+                physical_address, ..
+            } => {
+                event.kind = EventKind::Hit;
+                event.frame = (physical_address / page_size) as i32;
+            }
+            AccessOutcome::Fault {
+                physical_address,
+                evicted_page,
+                ..
+            } => {
+                let page = page_number as usize;
+                event.kind = if self.seen[page] {
+                    EventKind::CapacityMiss
+                } else {
+                    EventKind::ColdMiss
+                };
+                self.seen[page] = true;
+                event.frame = (physical_address / page_size) as i32;
+                event.evicted_page = evicted_page;
+            }
+            AccessOutcome::NoFrame { .. } => event.kind = EventKind::NoFrame,
+            AccessOutcome::OutOfRange { .. } => {}
+        }
+        Some(event)
+    }
+}
+
+// Pages already valid in `page_table` count as referenced before.
+pub fn fault_events<R>(
+    policy: ReplacementPolicy,
+    page_table: Vec<PTE>,
+    frame_pool: Vec<i32>,
+    page_references: R,
+) -> FaultEvents<R::IntoIter>
+where
+    R: IntoIterator<Item = i32>,
+{
+    let seen = page_table.iter().map(|pte| pte.is_valid).collect();
+    FaultEvents {
+        references: page_references.into_iter(),
+        translator: AddressTranslator::from_parts(1, page_table, frame_pool, policy),
+        seen,
+        index: 0,
+    }
+}
+
+pub fn fault_events_fifo<R>(
+    page_table: Vec<PTE>,
+    frame_pool: Vec<i32>,
+    page_references: R,
+) -> FaultEvents<R::IntoIter>
+where
+    R: IntoIterator<Item = i32>,
+{
+    fault_events(
+        ReplacementPolicy::Fifo,
+        page_table,
+        frame_pool,
+        page_references,
+    )
+}
+
+pub fn fault_events_lru<R>(
+    page_table: Vec<PTE>,
+    frame_pool: Vec<i32>,
+    page_references: R,
+) -> FaultEvents<R::IntoIter>
+where
+    R: IntoIterator<Item = i32>,
+{
+    fault_events(
+        ReplacementPolicy::Lru,
+        page_table,
+        frame_pool,
+        page_references,
+    )
+}
+
+pub fn fault_events_lfu<R>(
+    page_table: Vec<PTE>,
+    frame_pool: Vec<i32>,
+    page_references: R,
+) -> FaultEvents<R::IntoIter>
+where
+    R: IntoIterator<Item = i32>,
+{
+    fault_events(
+        ReplacementPolicy::Lfu,
+        page_table,
+        frame_pool,
+        page_references,
+    )
+}
+
+#[derive(Deserialize, Serialize, Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct EventSummary {
+    pub accesses: usize,
+    pub hits: usize,
+    pub cold_misses: usize,
+    pub capacity_misses: usize,
+    pub evictions: usize,
+    pub no_frame: usize,
+    pub out_of_range: usize,
+}
+
+impl EventSummary {
+    pub fn faults(&self) -> usize {
+        self.cold_misses + self.capacity_misses + self.no_frame
+    }
+
+    pub fn record(&mut self, event: &PageEvent) {
+        self.accesses += 1;
+        match event.kind {
+            EventKind::Hit => self.hits += 1,
+            EventKind::ColdMiss => self.cold_misses += 1,
+            EventKind::CapacityMiss => self.capacity_misses += 1,
+            EventKind::NoFrame => self.no_frame += 1,
+            EventKind::OutOfRange => self.out_of_range += 1,
+        }
+        if event.evicted_page.is_some() {
+            self.evictions += 1;
+        }
+    }
+}
+
+pub fn summarize_events<I>(events: I) -> EventSummary
+where
+    I: IntoIterator<Item = PageEvent>,
+{
+    let mut summary = EventSummary::default();
+    for event in events {
+        summary.record(&event);
+    }
+    summary
+}
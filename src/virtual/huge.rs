@@ -0,0 +1,453 @@
+use crate::r#virtual::r#virtual::PTE;
+use crate::r#virtual::tlb::{Tlb, TlbConfig, TlbReplacement};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+
+pub const BASE_PAGE_BYTES: u64 = 4096;
+
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PageSize {
+    Base4K,
+    Huge2M,
+    Huge1G,
+}
+
+impl PageSize {
+    pub const ALL: [PageSize; 3] = [PageSize::Base4K, PageSize::Huge2M, PageSize::Huge1G];
+
+    pub fn bytes(self) -> u64 {
+        match self {
+            PageSize::Base4K => BASE_PAGE_BYTES,
+            PageSize::Huge2M => 2 << 20,
+            PageSize::Huge1G => 1 << 30,
+        }
+    }
+
+    pub fn base_pages(self) -> usize {
+        (self.bytes() / BASE_PAGE_BYTES) as usize
+    }
+
+    fn index(self) -> usize {
+        match self {
+            PageSize::Base4K => 0,
+            PageSize::Huge2M => 1,
+            PageSize::Huge1G => 2,
+        }
+    }
+}
+
+pub fn tlb_reach(entries: usize, page_size: PageSize) -> u64 {
+    entries as u64 * page_size.bytes()
+}
+
+// Physical memory as an array of 4 KiB frames. A page of any size takes a
+// run of contiguous frames whose first frame is aligned to the page size.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ContiguousFrameAllocator {
+    pub used: Vec<bool>,
+}
+
+impl ContiguousFrameAllocator {
+    pub fn new(frame_count: usize) -> Self {
+        ContiguousFrameAllocator {
+            used: vec![false; frame_count],
+        }
+    }
+
+    pub fn free_frames(&self) -> usize {
+        self.used.iter().filter(|&&used| !used).count()
+    }
+
+    fn run_is_free(&self, start: usize, length: usize) -> bool {
+        start + length <= self.used.len() && self.used[start..start + length].iter().all(|&u| !u)
+    }
+
+    // First fit over aligned positions.
+    pub fn allocate(&mut self, page_size: PageSize) -> Option<i32> {
+        let length = page_size.base_pages();
+        let start = (0..self.used.len())
+            .step_by(length)
+            .find(|&start| self.run_is_free(start, length))?;
+        for used in self.used[start..start + length].iter_mut() {
+            *used = true;
+        }
+        Some(start as i32)
+    }
+
+    pub fn free(&mut self, frame_number: i32, page_size: PageSize) {
+        let start = frame_number as usize;
+        for used in self.used[start..start + page_size.base_pages()].iter_mut() {
+            *used = false;
+        }
+    }
+
+    // How many pages of the given size could still be allocated.
+    pub fn available(&self, page_size: PageSize) -> usize {
+        let length = page_size.base_pages();
+        (0..self.used.len())
+            .step_by(length)
+            .filter(|&start| self.run_is_free(start, length))
+            .count()
+    }
+
+    // Share of free memory that cannot back a page of the given size because
+    // it is not part of a free aligned run.
+    pub fn fragmentation(&self, page_size: PageSize) -> f64 {
+        let free = self.free_frames();
+        if free == 0 {
+            return 0.0;
+        }
+        let usable = self.available(page_size) * page_size.base_pages();
+        1.0 - usable as f64 / free as f64
+    }
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PageSizeMode {
+    BaseOnly,
+    Huge2M,
+    Huge1G,
+    // Maps 4 KiB pages and promotes a 2 MiB region once enough of it has
+    // been touched; `end_epoch` demotes huge pages that went sparse.
+    Transparent,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq)]
+pub struct HugePageConfig {
+    pub memory_frames: usize,
+    pub mode: PageSizeMode,
+    pub tlb_entries: [usize; 3],
+    pub promotion_threshold: f64,
+    pub demotion_threshold: f64,
+}
+
+impl Default for HugePageConfig {
+    fn default() -> Self {
+        HugePageConfig {
+            memory_frames: 1 << 18,
+            mode: PageSizeMode::BaseOnly,
+            tlb_entries: [64, 32, 4],
+            promotion_threshold: 0.5,
+            demotion_threshold: 0.1,
+        }
+    }
+}
+
+impl HugePageConfig {
+    pub fn tlb_reach(&self) -> u64 {
+        PageSize::ALL
+            .iter()
+            .map(|&size| tlb_reach(self.tlb_entries[size.index()], size))
+            .sum()
+    }
+}
+
+#[derive(Deserialize, Serialize, Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct HugePageStats {
+    pub accesses: usize,
+    pub tlb_hits: usize,
+    pub tlb_misses: usize,
+    pub page_faults: usize,
+    pub huge_fallbacks: usize,
+    pub promotions: usize,
+    pub demotions: usize,
+    pub out_of_memory: usize,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HugeAccess {
+    Hit {
+        page_size: PageSize,
+        physical_address: u64,
+    },
+    Fault {
+        page_size: PageSize,
+        physical_address: u64,
+    },
+    OutOfMemory,
+}
+
+// Mixed page size page table with one TLB per page size, as on x86-64.
+// Mappings are keyed by (size, virtual address / size) and their PTE frame is
+// the first 4 KiB frame of the run. Pages are never evicted; a fault that
+// finds no free frames reports `OutOfMemory`.
+#[derive(Debug, Clone)]
+pub struct HugePageSimulator {
+    pub config: HugePageConfig,
+    pub allocator: ContiguousFrameAllocator,
+    pub mappings: HashMap<(PageSize, u64), PTE>,
+    pub tlbs: Vec<Tlb>,
+    pub stats: HugePageStats,
+    pub touched: HashSet<u64>,
+    smaller_mappings: HashMap<(PageSize, u64), usize>,
+    epoch_touched: HashMap<u64, HashSet<u64>>,
+    current_timestamp: i32,
+}
+
+impl HugePageSimulator {
+    pub fn new(config: HugePageConfig) -> Self {
+        let tlbs = config
+            .tlb_entries
+            .iter()
+            .map(|&entries| {
+                Tlb::new(TlbConfig {
+                    entries,
+                    associativity: entries,
+                    replacement: TlbReplacement::Lru,
+                    asid_tagged: true,
+                })
+            })
+            .collect();
+        HugePageSimulator {
+            config,
+            allocator: ContiguousFrameAllocator::new(config.memory_frames),
+            mappings: HashMap::new(),
+            tlbs,
+            stats: HugePageStats::default(),
+            touched: HashSet::new(),
+            smaller_mappings: HashMap::new(),
+            epoch_touched: HashMap::new(),
+            current_timestamp: 1,
+        }
+    }
+
+    fn mapping_for(&self, virtual_address: u64) -> Option<(PageSize, PTE)> {
+        PageSize::ALL.iter().rev().find_map(|&size| {
+            self.mappings
+                .get(&(size, virtual_address / size.bytes()))
+                .map(|pte| (size, *pte))
+        })
+    }
+
+    // Number of mappings of a smaller size inside the region of `page_size`
+    // that contains `virtual_address`.
+    fn mappings_within(&self, virtual_address: u64, page_size: PageSize) -> usize {
+        let region = virtual_address / page_size.bytes();
+        self.smaller_mappings
+            .get(&(page_size, region))
+            .copied()
+            .unwrap_or(0)
+    }
+
+    fn count_within_larger(&mut self, virtual_address: u64, page_size: PageSize, mapped: bool) {
+        for &larger in PageSize::ALL
+            .iter()
+            .filter(|s| s.bytes() > page_size.bytes())
+        {
+            let count = self
+                .smaller_mappings
+                .entry((larger, virtual_address / larger.bytes()))
+                .or_insert(0);
+            if mapped {
+                *count += 1;
+            } else {
+                *count -= 1;
+            }
+        }
+    }
+
+    fn map(&mut self, virtual_address: u64, page_size: PageSize) -> Option<PTE> {
+        let frame_number = self.allocator.allocate(page_size)?;
+        let pte = PTE {
+            is_valid: true,
+            frame_number,
+            arrival_timestamp: self.current_timestamp,
+            last_access_timestamp: self.current_timestamp,
+            reference_count: 0,
+            ..PTE::default()
+        };
+        self.mappings
+            .insert((page_size, virtual_address / page_size.bytes()), pte);
+        self.count_within_larger(virtual_address, page_size, true);
+        Some(pte)
+    }
+
+    fn unmap(&mut self, page_size: PageSize, page_number: u64) {
+        if let Some(pte) = self.mappings.remove(&(page_size, page_number)) {
+            self.allocator.free(pte.frame_number, page_size);
+            self.tlbs[page_size.index()].invalidate(page_number);
+            self.count_within_larger(page_number * page_size.bytes(), page_size, false);
+        }
+    }
+
+    fn fault_in(&mut self, virtual_address: u64) -> Option<(PageSize, PTE)> {
+        let preferred = match self.config.mode {
+            PageSizeMode::BaseOnly | PageSizeMode::Transparent => PageSize::Base4K,
+            PageSizeMode::Huge2M => PageSize::Huge2M,
+            PageSizeMode::Huge1G => PageSize::Huge1G,
+        };
+        for &size in PageSize::ALL.iter().rev() {
+            if size.bytes() > preferred.bytes() || self.mappings_within(virtual_address, size) > 0 {
+                continue;
+            }
+            if let Some(pte) = self.map(virtual_address, size) {
+                if size != preferred {
+                    self.stats.huge_fallbacks += 1;
+                }
+                return Some((size, pte));
+            }
+        }
+        None
+    }
+
+    pub fn access(&mut self, virtual_address: u64) -> HugeAccess {
+        self.stats.accesses += 1;
+        let base_page = virtual_address / BASE_PAGE_BYTES;
+        self.touched.insert(base_page);
+        self.epoch_touched
+            .entry(virtual_address / PageSize::Huge2M.bytes())
+            .or_default()
+            .insert(base_page);
+
+        let (page_size, faulted) = match self.mapping_for(virtual_address) {
+            Some((size, _)) => (size, false),
+            None => match self.fault_in(virtual_address) {
+                Some((size, _)) => {
+                    self.stats.page_faults += 1;
+                    (size, true)
+                }
+                None => {
+                    self.stats.out_of_memory += 1;
+                    return HugeAccess::OutOfMemory;
+                }
+            },
+        };
+        let page_number = virtual_address / page_size.bytes();
+        let tlb = &mut self.tlbs[page_size.index()];
+        let pte = self
+            .mappings
+            .get_mut(&(page_size, page_number))
+            .expect("mapped above");
+        pte.last_access_timestamp = self.current_timestamp;
+        pte.reference_count += 1;
+        self.current_timestamp += 1;
+        let frame_number = pte.frame_number;
+        if tlb.lookup(page_number).is_some() {
+            self.stats.tlb_hits += 1;
+        } else {
+            self.stats.tlb_misses += 1;
+            tlb.insert(page_number, frame_number);
+        }
+
+        let physical_address =
+            frame_number as u64 * BASE_PAGE_BYTES + virtual_address % page_size.bytes();
+        if faulted && self.config.mode == PageSizeMode::Transparent && page_size == PageSize::Base4K
+        {
+            self.try_promote(virtual_address);
+        }
+        if faulted {
+            HugeAccess::Fault {
+                page_size,
+                physical_address,
+            }
+        } else {
+            HugeAccess::Hit {
+                page_size,
+                physical_address,
+            }
+        }
+    }
+
+    // Replaces the 4 KiB mappings of a 2 MiB region with one huge page once
+    // the share of its base pages mapped reaches `promotion_threshold`.
+    fn try_promote(&mut self, virtual_address: u64) {
+        let huge = PageSize::Huge2M;
+        let region = virtual_address / huge.bytes();
+        let mapped = self.mappings_within(virtual_address, huge);
+        if (mapped as f64) < self.config.promotion_threshold * huge.base_pages() as f64
+            || self.allocator.available(huge) == 0
+        {
+            return;
+        }
+        let first = region * huge.base_pages() as u64;
+        for page_number in first..first + huge.base_pages() as u64 {
+            self.unmap(PageSize::Base4K, page_number);
+        }
+        if self.map(virtual_address, huge).is_some() {
+            self.stats.promotions += 1;
+        }
+    }
+
+    // Closes an access-density epoch. In transparent mode every 2 MiB page
+    // with fewer than `demotion_threshold` of its base pages touched during
+    // the epoch is split, keeping only the touched base pages mapped.
+    pub fn end_epoch(&mut self) {
+        let epoch_touched = std::mem::take(&mut self.epoch_touched);
+        if self.config.mode != PageSizeMode::Transparent {
+            return;
+        }
+        let huge = PageSize::Huge2M;
+        let mut regions: Vec<u64> = self
+            .mappings
+            .keys()
+            .filter(|(size, _)| *size == huge)
+            .map(|&(_, region)| region)
+            .collect();
+        regions.sort_unstable();
+        let empty = HashSet::new();
+        for region in regions {
+            let pages = epoch_touched.get(&region).unwrap_or(&empty);
+            let density = pages.len() as f64 / huge.base_pages() as f64;
+            if density >= self.config.demotion_threshold {
+                continue;
+            }
+            self.unmap(huge, region);
+            let mut pages: Vec<u64> = pages.iter().copied().collect();
+            pages.sort_unstable();
+            for page_number in pages {
+                self.map(page_number * BASE_PAGE_BYTES, PageSize::Base4K);
+            }
+            self.stats.demotions += 1;
+        }
+    }
+
+    pub fn mapped_bytes(&self) -> u64 {
+        self.mappings.keys().map(|(size, _)| size.bytes()).sum()
+    }
+
+    // Mapped memory that was never touched.
+    pub fn internal_fragmentation_bytes(&self) -> u64 {
+        let touched_mapped = self
+            .touched
+            .iter()
+            .filter(|&&page| self.mapping_for(page * BASE_PAGE_BYTES).is_some())
+            .count() as u64;
+        self.mapped_bytes() - touched_mapped * BASE_PAGE_BYTES
+    }
+
+    pub fn run(&mut self, virtual_addresses: &[u64]) -> HugePageStats {
+        for &virtual_address in virtual_addresses {
+            self.access(virtual_address);
+        }
+        self.stats
+    }
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq)]
+pub struct PageSizeComparison {
+    pub mode: PageSizeMode,
+    pub stats: HugePageStats,
+    pub mapped_bytes: u64,
+    pub internal_fragmentation_bytes: u64,
+}
+
+// Runs the same addresses once per mode, e.g. 4 KiB only against 2 MiB.
+pub fn compare_page_sizes(
+    config: HugePageConfig,
+    modes: &[PageSizeMode],
+    virtual_addresses: &[u64],
+) -> Vec<PageSizeComparison> {
+    modes
+        .iter()
+        .map(|&mode| {
+            let mut simulator = HugePageSimulator::new(HugePageConfig { mode, ..config });
+            simulator.run(virtual_addresses);
+            PageSizeComparison {
+                mode,
+                stats: simulator.stats,
+                mapped_bytes: simulator.mapped_bytes(),
+                internal_fragmentation_bytes: simulator.internal_fragmentation_bytes(),
+            }
+        })
+        .collect()
+}
@@ -0,0 +1,249 @@
+use crate::r#virtual::r#virtual::{select_victim, selects_from_pte_fields, ReplacementPolicy, PTE};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MappingKind {
+    Anonymous,
+    File { file_id: u32, file_offset: usize },
+}
+
+// A virtual memory area of `page_count` pages starting at `start_page`.
+// File pages `start_page + i` are backed by page `file_offset + i` of the
+// file.
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Mapping {
+    pub start_page: usize,
+    pub page_count: usize,
+    pub kind: MappingKind,
+}
+
+impl Mapping {
+    pub fn contains(&self, page_number: usize) -> bool {
+        page_number >= self.start_page && page_number < self.start_page + self.page_count
+    }
+}
+
+// What a resident frame holds. File pages live in the page cache and are
+// shared by every process mapping the same file page.
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PageKey {
+    File { file_id: u32, file_page: usize },
+    Anonymous { pid: i32, page_number: usize },
+}
+
+#[derive(Deserialize, Serialize, Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct PageCacheStats {
+    pub cache_hits: usize,
+    pub cache_misses: usize,
+    pub anonymous_hits: usize,
+    pub zero_fill_faults: usize,
+    pub clean_drops: usize,
+    pub dirty_flushes: usize,
+    pub swap_outs: usize,
+    pub swap_ins: usize,
+}
+
+impl PageCacheStats {
+    pub fn page_cache_hit_rate(&self) -> f64 {
+        let lookups = self.cache_hits + self.cache_misses;
+        if lookups == 0 {
+            0.0
+        } else {
+            self.cache_hits as f64 / lookups as f64
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MmapOutcome {
+    Hit {
+        frame: i32,
+    },
+    Fault {
+        frame: i32,
+        evicted: Option<PageKey>,
+    },
+    Segfault,
+    NoFrame,
+}
+
+// File-backed and anonymous pages competing for one frame pool. Evicting a
+// clean file page just drops it, a dirty one is flushed to its file first,
+// and an anonymous page always goes to swap. Victims are chosen by
+// `select_victim`, so the policy must be FIFO, LRU, LFU or MRU; `new`
+// rejects any other.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MmapMemory {
+    pub policy: ReplacementPolicy,
+    pub frame_pool: Vec<i32>,
+    pub resident: HashMap<PageKey, PTE>,
+    pub mappings: HashMap<i32, Vec<Mapping>>,
+    pub swapped: HashSet<PageKey>,
+    pub stats: PageCacheStats,
+    pub current_timestamp: i32,
+}
+
+impl MmapMemory {
+    pub fn new(frame_count: usize, policy: ReplacementPolicy) -> Self {
+        assert!(
+            selects_from_pte_fields(policy),
+            "mmap memory supports FIFO, LRU, LFU and MRU, not {}",
+            policy.name()
+        );
+        MmapMemory {
+            policy,
+            frame_pool: (0..frame_count as i32).rev().collect(),
+            resident: HashMap::new(),
+            mappings: HashMap::new(),
+            swapped: HashSet::new(),
+            stats: PageCacheStats::default(),
+            current_timestamp: 1,
+        }
+    }
+
+    // Adds a mapping to a process, refusing one that overlaps an existing
+    // mapping.
+    pub fn mmap(&mut self, pid: i32, mapping: Mapping) -> bool {
+        let mappings = self.mappings.entry(pid).or_default();
+        let end = mapping.start_page + mapping.page_count;
+        if mapping.page_count == 0
+            || mappings
+                .iter()
+                .any(|m| mapping.start_page < m.start_page + m.page_count && m.start_page < end)
+        {
+            return false;
+        }
+        mappings.push(mapping);
+        true
+    }
+
+    // Anonymous pages of the mapping are discarded along with their swap
+    // slots; file pages stay in the page cache for other mappers.
+    pub fn munmap(&mut self, pid: i32, start_page: usize) -> bool {
+        let mappings = match self.mappings.get_mut(&pid) {
+            Some(mappings) => mappings,
+            None => return false,
+        };
+        let index = match mappings.iter().position(|m| m.start_page == start_page) {
+            Some(index) => index,
+            None => return false,
+        };
+        let mapping = mappings.remove(index);
+        if mapping.kind == MappingKind::Anonymous {
+            for page_number in start_page..start_page + mapping.page_count {
+                let key = PageKey::Anonymous { pid, page_number };
+                self.swapped.remove(&key);
+                if let Some(pte) = self.resident.remove(&key) {
+                    self.frame_pool.push(pte.frame_number);
+                }
+            }
+        }
+        true
+    }
+
+    pub fn page_key(&self, pid: i32, page_number: usize) -> Option<PageKey> {
+        let mapping = self
+            .mappings
+            .get(&pid)?
+            .iter()
+            .find(|m| m.contains(page_number))?;
+        Some(match mapping.kind {
+            MappingKind::Anonymous => PageKey::Anonymous { pid, page_number },
+            MappingKind::File {
+                file_id,
+                file_offset,
+            } => PageKey::File {
+                file_id,
+                file_page: file_offset + page_number - mapping.start_page,
+            },
+        })
+    }
+
+    fn evict(&mut self) -> Option<(i32, PageKey)> {
+        let keys: Vec<PageKey> = self.resident.keys().copied().collect();
+        let ptes: Vec<PTE> = keys.iter().map(|key| self.resident[key]).collect();
+        let key = keys[select_victim(self.policy, &ptes)?];
+        let pte = self.resident.remove(&key)?;
+        match key {
+            PageKey::File { .. } if pte.is_dirty => self.stats.dirty_flushes += 1,
+            PageKey::File { .. } => self.stats.clean_drops += 1,
+            PageKey::Anonymous { .. } => {
+                self.stats.swap_outs += 1;
+                self.swapped.insert(key);
+            }
+        }
+        Some((pte.frame_number, key))
+    }
+
+    pub fn access(&mut self, pid: i32, page_number: usize, is_write: bool) -> MmapOutcome {
+        let key = match self.page_key(pid, page_number) {
+            Some(key) => key,
+            None => return MmapOutcome::Segfault,
+        };
+        let current_timestamp = self.current_timestamp;
+        self.current_timestamp += 1;
+
+        if let Some(pte) = self.resident.get_mut(&key) {
+            pte.last_access_timestamp = current_timestamp;
+            pte.reference_count += 1;
+            pte.is_dirty |= is_write;
+            match key {
+                PageKey::File { .. } => self.stats.cache_hits += 1,
+                PageKey::Anonymous { .. } => self.stats.anonymous_hits += 1,
+            }
+            return MmapOutcome::Hit {
+                frame: pte.frame_number,
+            };
+        }
+
+        let (frame, evicted) = match self.frame_pool.pop() {
+            Some(frame) => (frame, None),
+            None => match self.evict() {
+                Some((frame, victim)) => (frame, Some(victim)),
+                None => return MmapOutcome::NoFrame,
+            },
+        };
+        match key {
+            PageKey::File { .. } => self.stats.cache_misses += 1,
+            PageKey::Anonymous { .. } if self.swapped.remove(&key) => self.stats.swap_ins += 1,
+            PageKey::Anonymous { .. } => self.stats.zero_fill_faults += 1,
+        }
+        self.resident.insert(
+            key,
+            PTE {
+                is_valid: true,
+                frame_number: frame,
+                arrival_timestamp: current_timestamp,
+                last_access_timestamp: current_timestamp,
+                reference_count: 1,
+                is_dirty: is_write,
+                ..PTE::default()
+            },
+        );
+        MmapOutcome::Fault { frame, evicted }
+    }
+
+    // Writes every dirty cached page of the file back, leaving them resident
+    // and clean. Returns the number of pages written.
+    pub fn msync(&mut self, file_id: u32) -> usize {
+        let mut flushed = 0;
+        for (key, pte) in self.resident.iter_mut() {
+            if let PageKey::File { file_id: id, .. } = key {
+                if *id == file_id && pte.is_dirty {
+                    pte.is_dirty = false;
+                    flushed += 1;
+                }
+            }
+        }
+        self.stats.dirty_flushes += flushed;
+        flushed
+    }
+
+    pub fn cached_pages(&self, file_id: u32) -> usize {
+        self.resident
+            .keys()
+            .filter(|key| matches!(key, PageKey::File { file_id: id, .. } if *id == file_id))
+            .count()
+    }
+}
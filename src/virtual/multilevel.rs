@@ -0,0 +1,286 @@
+use crate::r#virtual::r#virtual::PTE;
+
+// `level_bits[0]` indexes the root table; the last level holds the PTEs. An
+// x86-64 style 48-bit space is `level_bits: vec![9, 9, 9, 9]`, `offset_bits: 12`.
+// Each table is allocated whole, so a single level may index at most
+// 2^MAX_LEVEL_BITS entries.
+pub const MAX_LEVEL_BITS: u32 = 24;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MultiLevelConfig {
+    pub level_bits: Vec<u32>,
+    pub offset_bits: u32,
+    pub entry_size: usize,
+}
+
+impl MultiLevelConfig {
+    pub fn new(level_bits: Vec<u32>, offset_bits: u32, entry_size: usize) -> Self {
+        let config = MultiLevelConfig {
+            level_bits,
+            offset_bits,
+            entry_size,
+        };
+        config.validate();
+        config
+    }
+
+    // Panics unless there is at least one level, every level fits in
+    // MAX_LEVEL_BITS, and levels plus offset fit in a 64-bit address.
+    pub fn validate(&self) {
+        assert!(
+            !self.level_bits.is_empty(),
+            "a page table needs at least one level"
+        );
+        assert!(
+            self.level_bits.iter().all(|&bits| bits <= MAX_LEVEL_BITS),
+            "a page table level may use at most {} bits",
+            MAX_LEVEL_BITS
+        );
+        assert!(
+            self.offset_bits < 64 && self.address_bits() <= 64,
+            "levels and offset must fit in a 64-bit address"
+        );
+    }
+
+    pub fn two_level_32bit() -> Self {
+        MultiLevelConfig {
+            level_bits: vec![10, 10],
+            offset_bits: 12,
+            entry_size: 4,
+        }
+    }
+
+    pub fn x86_64_four_level() -> Self {
+        MultiLevelConfig {
+            level_bits: vec![9, 9, 9, 9],
+            offset_bits: 12,
+            entry_size: 8,
+        }
+    }
+
+    pub fn address_bits(&self) -> u32 {
+        self.level_bits.iter().sum::<u32>() + self.offset_bits
+    }
+
+    pub fn page_size(&self) -> u64 {
+        1u64 << self.offset_bits
+    }
+
+    pub fn split_address(&self, virtual_address: u64) -> (Vec<usize>, u64) {
+        let offset = virtual_address & low_mask(self.offset_bits);
+        let mut remaining = virtual_address >> self.offset_bits;
+        let mut indices = vec![0; self.level_bits.len()];
+        for (level, &bits) in self.level_bits.iter().enumerate().rev() {
+            indices[level] = (remaining & low_mask(bits)) as usize;
+            remaining = remaining.checked_shr(bits).unwrap_or(0);
+        }
+        (indices, offset)
+    }
+}
+
+fn low_mask(bits: u32) -> u64 {
+    if bits >= 64 {
+        u64::MAX
+    } else {
+        (1u64 << bits) - 1
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PageTableNode {
+    Directory(Vec<Option<usize>>),
+    Leaf(Vec<PTE>),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Translation {
+    pub physical_address: u64,
+    pub frame_number: i32,
+    pub memory_accesses: usize,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TranslationFault {
+    AddressOutOfRange,
+    NotMapped {
+        level: usize,
+        memory_accesses: usize,
+    },
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct WalkStats {
+    pub translations: usize,
+    pub faults: usize,
+    pub memory_accesses: usize,
+}
+
+impl WalkStats {
+    pub fn average_memory_accesses(&self) -> f64 {
+        let walks = self.translations + self.faults;
+        if walks == 0 {
+            0.0
+        } else {
+            self.memory_accesses as f64 / walks as f64
+        }
+    }
+}
+
+// Tables live in `nodes`, with `nodes[0]` the root; directory entries hold the
+// index of the next-level table.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MultiLevelPageTable {
+    pub config: MultiLevelConfig,
+    pub nodes: Vec<PageTableNode>,
+    pub stats: WalkStats,
+}
+
+impl MultiLevelPageTable {
+    pub fn new(config: MultiLevelConfig) -> Self {
+        config.validate();
+        let root = MultiLevelPageTable::empty_node(&config, 0);
+        MultiLevelPageTable {
+            config,
+            nodes: vec![root],
+            stats: WalkStats::default(),
+        }
+    }
+
+    fn empty_node(config: &MultiLevelConfig, level: usize) -> PageTableNode {
+        let entries = 1usize << config.level_bits[level];
+        if level + 1 == config.level_bits.len() {
+            PageTableNode::Leaf(vec![PTE::default(); entries])
+        } else {
+            PageTableNode::Directory(vec![None; entries])
+        }
+    }
+
+    fn in_range(&self, virtual_address: u64) -> bool {
+        self.config.address_bits() == 64 || virtual_address >> self.config.address_bits() == 0
+    }
+
+    fn leaf_for(&mut self, indices: &[usize]) -> usize {
+        let mut node = 0;
+        for (level, &index) in indices.iter().enumerate().take(indices.len() - 1) {
+            let child = match &self.nodes[node] {
+                PageTableNode::Directory(entries) => entries[index],
+                PageTableNode::Leaf(_) => unreachable!(),
+            };
+            node = match child {
+                Some(child) => child,
+                None => {
+                    let child = self.nodes.len();
+                    let table = MultiLevelPageTable::empty_node(&self.config, level + 1);
+                    self.nodes.push(table);
+                    if let PageTableNode::Directory(entries) = &mut self.nodes[node] {
+                        entries[index] = Some(child);
+                    }
+                    child
+                }
+            };
+        }
+        node
+    }
+
+    // Maps the page containing `virtual_address` to `frame_number`, allocating
+    // any missing intermediate tables. Returns false if the address is out of range.
+    pub fn map(&mut self, virtual_address: u64, frame_number: i32) -> bool {
+        if !self.in_range(virtual_address) {
+            return false;
+        }
+        let (indices, _offset) = self.config.split_address(virtual_address);
+        let leaf = self.leaf_for(&indices);
+        if let PageTableNode::Leaf(entries) = &mut self.nodes[leaf] {
+            entries[indices[indices.len() - 1]] = PTE {
+                is_valid: true,
+                frame_number,
+                ..PTE::default()
+            };
+        }
+        true
+    }
+
+    pub fn unmap(&mut self, virtual_address: u64) -> Option<PTE> {
+        let pte = self.lookup(virtual_address)?;
+        let (indices, _offset) = self.config.split_address(virtual_address);
+        let leaf = self.leaf_for(&indices);
+        if let PageTableNode::Leaf(entries) = &mut self.nodes[leaf] {
+            entries[indices[indices.len() - 1]] = PTE::default();
+        }
+        Some(pte)
+    }
+
+    pub fn lookup(&self, virtual_address: u64) -> Option<PTE> {
+        if !self.in_range(virtual_address) {
+            return None;
+        }
+        let (indices, _offset) = self.config.split_address(virtual_address);
+        let mut node = 0;
+        for &index in indices.iter() {
+            match &self.nodes[node] {
+                PageTableNode::Directory(entries) => node = entries[index]?,
+                PageTableNode::Leaf(entries) => {
+                    return Some(entries[index]).filter(|pte| pte.is_valid);
+                }
+            }
+        }
+        None
+    }
+
+    pub fn translate(&mut self, virtual_address: u64) -> Result<Translation, TranslationFault> {
+        if !self.in_range(virtual_address) {
+            return Err(TranslationFault::AddressOutOfRange);
+        }
+        let (indices, offset) = self.config.split_address(virtual_address);
+        let mut node = 0;
+        for (level, &index) in indices.iter().enumerate() {
+            let memory_accesses = level + 1;
+            self.stats.memory_accesses += 1;
+            let next = match &self.nodes[node] {
+                PageTableNode::Directory(entries) => entries[index],
+                PageTableNode::Leaf(entries) if entries[index].is_valid => {
+                    let frame_number = entries[index].frame_number;
+                    self.stats.translations += 1;
+                    return Ok(Translation {
+                        physical_address: frame_number as u64 * self.config.page_size() + offset,
+                        frame_number,
+                        memory_accesses,
+                    });
+                }
+                PageTableNode::Leaf(_) => None,
+            };
+            match next {
+                Some(child) => node = child,
+                None => {
+                    self.stats.faults += 1;
+                    return Err(TranslationFault::NotMapped {
+                        level,
+                        memory_accesses,
+                    });
+                }
+            }
+        }
+        unreachable!()
+    }
+
+    pub fn table_count(&self) -> usize {
+        self.nodes.len()
+    }
+
+    pub fn memory_overhead_bytes(&self) -> usize {
+        self.nodes
+            .iter()
+            .map(|node| match node {
+                PageTableNode::Directory(entries) => entries.len(),
+                PageTableNode::Leaf(entries) => entries.len(),
+            })
+            .sum::<usize>()
+            * self.config.entry_size
+    }
+
+    // Size of a single flat table covering the same address space, for comparison.
+    pub fn flat_table_bytes(&self) -> u128 {
+        let entries = 1u128 << self.config.level_bits.iter().sum::<u32>();
+        entries * self.config.entry_size as u128
+    }
+}
@@ -0,0 +1,340 @@
+use crate::r#virtual::r#virtual::{
+    first_timestamp, select_victim, selects_from_pte_fields, ReplacementPolicy, PTE,
+};
+use std::mem::size_of;
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct ProbeStats {
+    pub lookups: usize,
+    pub probes: usize,
+}
+
+impl ProbeStats {
+    pub fn average_probe_length(&self) -> f64 {
+        if self.lookups == 0 {
+            0.0
+        } else {
+            self.probes as f64 / self.lookups as f64
+        }
+    }
+}
+
+// The operations the replacement code needs from a page table, keyed by
+// process id and page number so per-process and system-wide designs compare.
+pub trait PageMap {
+    fn lookup(&mut self, pid: i32, page_number: usize) -> Option<PTE>;
+    fn insert(&mut self, pid: i32, page_number: usize, pte: PTE) -> bool;
+    fn evict(&mut self, pid: i32, page_number: usize) -> Option<PTE>;
+    fn resident(&self) -> Vec<(i32, usize, PTE)>;
+    fn stats(&self) -> ProbeStats;
+    fn memory_footprint_bytes(&self) -> usize;
+}
+
+// The existing design: one `Vec<PTE>` per process indexed by page number.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FlatPageTable {
+    pub page_count: usize,
+    pub tables: Vec<(i32, Vec<PTE>)>,
+    pub stats: ProbeStats,
+}
+
+impl FlatPageTable {
+    pub fn new(page_count: usize) -> Self {
+        FlatPageTable {
+            page_count,
+            tables: Vec::new(),
+            stats: ProbeStats::default(),
+        }
+    }
+
+    fn table_index(&mut self, pid: i32) -> usize {
+        match self.tables.iter().position(|(p, _)| *p == pid) {
+            Some(index) => index,
+            None => {
+                self.tables
+                    .push((pid, vec![PTE::default(); self.page_count]));
+                self.tables.len() - 1
+            }
+        }
+    }
+}
+
+impl PageMap for FlatPageTable {
+    fn lookup(&mut self, pid: i32, page_number: usize) -> Option<PTE> {
+        self.stats.lookups += 1;
+        self.stats.probes += 1;
+        let table = self.tables.iter().find(|(p, _)| *p == pid)?;
+        table.1.get(page_number).copied().filter(|pte| pte.is_valid)
+    }
+
+    fn insert(&mut self, pid: i32, page_number: usize, pte: PTE) -> bool {
+        if page_number >= self.page_count {
+            return false;
+        }
+        let index = self.table_index(pid);
+        self.tables[index].1[page_number] = pte;
+        true
+    }
+
+    fn evict(&mut self, pid: i32, page_number: usize) -> Option<PTE> {
+        let (_, table) = self.tables.iter_mut().find(|(p, _)| *p == pid)?;
+        let pte = table.get(page_number).copied().filter(|pte| pte.is_valid)?;
+        table[page_number] = PTE::default();
+        Some(pte)
+    }
+
+    fn resident(&self) -> Vec<(i32, usize, PTE)> {
+        let mut resident = Vec::new();
+        for (pid, table) in self.tables.iter() {
+            for (page_number, pte) in table.iter().enumerate() {
+                if pte.is_valid {
+                    resident.push((*pid, page_number, *pte));
+                }
+            }
+        }
+        resident
+    }
+
+    fn stats(&self) -> ProbeStats {
+        self.stats
+    }
+
+    fn memory_footprint_bytes(&self) -> usize {
+        self.tables.len() * self.page_count * size_of::<PTE>()
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MappedPage {
+    pub pid: i32,
+    pub page_number: usize,
+    pub pte: PTE,
+}
+
+// One slot per physical frame, indexed by frame number; a lookup scans the
+// frames for a matching (pid, page) pair.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InvertedPageTable {
+    pub frames: Vec<Option<MappedPage>>,
+    pub stats: ProbeStats,
+}
+
+impl InvertedPageTable {
+    pub fn new(frame_count: usize) -> Self {
+        InvertedPageTable {
+            frames: vec![None; frame_count],
+            stats: ProbeStats::default(),
+        }
+    }
+
+    fn find(&self, pid: i32, page_number: usize) -> (Option<usize>, usize) {
+        let mut probes = 0;
+        for (frame, slot) in self.frames.iter().enumerate() {
+            probes += 1;
+            if let Some(mapped) = slot {
+                if mapped.pid == pid && mapped.page_number == page_number {
+                    return (Some(frame), probes);
+                }
+            }
+        }
+        (None, probes)
+    }
+}
+
+impl PageMap for InvertedPageTable {
+    fn lookup(&mut self, pid: i32, page_number: usize) -> Option<PTE> {
+        let (frame, probes) = self.find(pid, page_number);
+        self.stats.lookups += 1;
+        self.stats.probes += probes;
+        frame.and_then(|f| self.frames[f]).map(|mapped| mapped.pte)
+    }
+
+    fn insert(&mut self, pid: i32, page_number: usize, pte: PTE) -> bool {
+        let frame = pte.frame_number;
+        if frame < 0 || frame as usize >= self.frames.len() {
+            return false;
+        }
+        if let (Some(previous), _) = self.find(pid, page_number) {
+            self.frames[previous] = None;
+        }
+        self.frames[frame as usize] = Some(MappedPage {
+            pid,
+            page_number,
+            pte,
+        });
+        true
+    }
+
+    fn evict(&mut self, pid: i32, page_number: usize) -> Option<PTE> {
+        let frame = self.find(pid, page_number).0?;
+        self.frames[frame].take().map(|mapped| mapped.pte)
+    }
+
+    fn resident(&self) -> Vec<(i32, usize, PTE)> {
+        self.frames
+            .iter()
+            .flatten()
+            .map(|mapped| (mapped.pid, mapped.page_number, mapped.pte))
+            .collect()
+    }
+
+    fn stats(&self) -> ProbeStats {
+        self.stats
+    }
+
+    fn memory_footprint_bytes(&self) -> usize {
+        self.frames.len() * size_of::<Option<MappedPage>>()
+    }
+}
+
+// Buckets of (pid, page) chains; the footprint counts a head pointer per bucket
+// and a chained node (entry plus next pointer) per resident page.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HashedPageTable {
+    pub buckets: Vec<Vec<MappedPage>>,
+    pub stats: ProbeStats,
+}
+
+impl HashedPageTable {
+    pub fn new(bucket_count: usize) -> Self {
+        HashedPageTable {
+            buckets: vec![Vec::new(); std::cmp::max(bucket_count, 1)],
+            stats: ProbeStats::default(),
+        }
+    }
+
+    fn bucket(&self, pid: i32, page_number: usize) -> usize {
+        let key = ((pid as u32 as u64) << 32) ^ page_number as u64;
+        let hash = key.wrapping_mul(0x9E37_79B9_7F4A_7C15);
+        ((hash >> 32) % self.buckets.len() as u64) as usize
+    }
+
+    pub fn longest_chain(&self) -> usize {
+        self.buckets.iter().map(|b| b.len()).max().unwrap_or(0)
+    }
+}
+
+impl PageMap for HashedPageTable {
+    fn lookup(&mut self, pid: i32, page_number: usize) -> Option<PTE> {
+        let bucket = self.bucket(pid, page_number);
+        self.stats.lookups += 1;
+        let chain = &self.buckets[bucket];
+        match chain
+            .iter()
+            .position(|m| m.pid == pid && m.page_number == page_number)
+        {
+            Some(position) => {
+                self.stats.probes += position + 1;
+                Some(chain[position].pte)
+            }
+            None => {
+                self.stats.probes += std::cmp::max(chain.len(), 1);
+                None
+            }
+        }
+    }
+
+    fn insert(&mut self, pid: i32, page_number: usize, pte: PTE) -> bool {
+        let bucket = self.bucket(pid, page_number);
+        let chain = &mut self.buckets[bucket];
+        let mapped = MappedPage {
+            pid,
+            page_number,
+            pte,
+        };
+        match chain
+            .iter_mut()
+            .find(|m| m.pid == pid && m.page_number == page_number)
+        {
+            Some(existing) => *existing = mapped,
+            None => chain.push(mapped),
+        }
+        true
+    }
+
+    fn evict(&mut self, pid: i32, page_number: usize) -> Option<PTE> {
+        let bucket = self.bucket(pid, page_number);
+        let chain = &mut self.buckets[bucket];
+        let position = chain
+            .iter()
+            .position(|m| m.pid == pid && m.page_number == page_number)?;
+        Some(chain.remove(position).pte)
+    }
+
+    fn resident(&self) -> Vec<(i32, usize, PTE)> {
+        self.buckets
+            .iter()
+            .flatten()
+            .map(|mapped| (mapped.pid, mapped.page_number, mapped.pte))
+            .collect()
+    }
+
+    fn stats(&self) -> ProbeStats {
+        self.stats
+    }
+
+    fn memory_footprint_bytes(&self) -> usize {
+        let entries: usize = self.buckets.iter().map(|b| b.len()).sum();
+        self.buckets.len() * size_of::<usize>()
+            + entries * (size_of::<MappedPage>() + size_of::<usize>())
+    }
+}
+
+// Same loop as the `count_page_faults_*` functions, but driven through any
+// `PageMap`. Returns None for policies that keep state outside the PTEs, since
+// `select_victim` cannot pick a victim for them.
+pub fn count_page_faults_page_map<M: PageMap + ?Sized>(
+    page_map: &mut M,
+    pid: i32,
+    page_references: Vec<i32>,
+    frame_pool: &mut Vec<i32>,
+    policy: ReplacementPolicy,
+) -> Option<i32> {
+    if !selects_from_pte_fields(policy) {
+        return None;
+    }
+    let mut faults: i32 = 0;
+    let resident: Vec<PTE> = page_map.resident().iter().map(|(_, _, pte)| *pte).collect();
+    let start_timestamp = first_timestamp(&resident);
+
+    for (current_timestamp, reference) in (start_timestamp..).zip(page_references) {
+        let page_number = reference as usize;
+        if let Some(mut pte) = page_map.lookup(pid, page_number) {
+            pte.last_access_timestamp = current_timestamp;
+            pte.reference_count += 1;
+            page_map.insert(pid, page_number, pte);
+            continue;
+        }
+
+        faults += 1;
+        let frame = match frame_pool.pop() {
+            Some(frame) => frame,
+            None => {
+                let resident = page_map.resident();
+                let ptes: Vec<PTE> = resident.iter().map(|(_, _, pte)| *pte).collect();
+                match select_victim(policy, &ptes) {
+                    Some(victim) => {
+                        let (victim_pid, victim_page, _) = resident[victim];
+                        page_map.evict(victim_pid, victim_page);
+                        ptes[victim].frame_number
+                    }
+                    None => continue,
+                }
+            }
+        };
+        page_map.insert(
+            pid,
+            page_number,
+            PTE {
+                is_valid: true,
+                frame_number: frame,
+                arrival_timestamp: current_timestamp,
+                last_access_timestamp: current_timestamp,
+                reference_count: 1,
+                ..PTE::default()
+            },
+        );
+    }
+
+    Some(faults)
+}
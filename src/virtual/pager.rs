@@ -0,0 +1,273 @@
+use crate::r#virtual::r#virtual::{select_victim, selects_from_pte_fields, ReplacementPolicy, PTE};
+use serde::{Deserialize, Serialize};
+
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReplacementScope {
+    Global,
+    Local,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FrameAllocation {
+    Equal,
+    Proportional,
+    // Limits are only changed through `set_frame_limit`; new processes start
+    // with none.
+    Manual,
+}
+
+#[derive(Deserialize, Serialize, Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct ProcessStats {
+    pub accesses: usize,
+    pub faults: usize,
+    pub pages_evicted: usize,
+}
+
+impl ProcessStats {
+    pub fn fault_rate(&self) -> f64 {
+        if self.accesses == 0 {
+            0.0
+        } else {
+            self.faults as f64 / self.accesses as f64
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Process {
+    pub pid: i32,
+    pub page_table: Vec<PTE>,
+    pub frame_limit: usize,
+    pub stats: ProcessStats,
+}
+
+impl Process {
+    pub fn resident_count(&self) -> usize {
+        self.page_table.iter().filter(|pte| pte.is_valid).count()
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PagerOutcome {
+    Hit {
+        frame: i32,
+    },
+    Fault {
+        frame: i32,
+        evicted: Option<(i32, usize)>,
+    },
+    NoFrame,
+    UnknownProcess,
+    OutOfRange,
+}
+
+// Pages many processes, each with its own page table, out of one frame pool.
+// With `Local` scope a faulting process only replaces its own pages once it
+// holds `frame_limit` frames; with `Global` scope the victim may belong to any
+// process and the limits are ignored. Victims are chosen by `select_victim`,
+// so the policy must be FIFO, LRU, LFU or MRU; `new` rejects any other.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MultiProcessPager {
+    pub policy: ReplacementPolicy,
+    pub scope: ReplacementScope,
+    pub allocation: FrameAllocation,
+    pub total_frames: usize,
+    pub processes: Vec<Process>,
+    pub frame_pool: Vec<i32>,
+    pub current_timestamp: i32,
+}
+
+impl MultiProcessPager {
+    pub fn new(
+        total_frames: usize,
+        policy: ReplacementPolicy,
+        scope: ReplacementScope,
+        allocation: FrameAllocation,
+    ) -> Self {
+        assert!(
+            selects_from_pte_fields(policy),
+            "the pager supports FIFO, LRU, LFU and MRU, not {}",
+            policy.name()
+        );
+        MultiProcessPager {
+            policy,
+            scope,
+            allocation,
+            total_frames,
+            processes: Vec::new(),
+            frame_pool: (0..total_frames as i32).rev().collect(),
+            current_timestamp: 1,
+        }
+    }
+
+    pub fn process(&self, pid: i32) -> Option<&Process> {
+        self.processes.iter().find(|p| p.pid == pid)
+    }
+
+    fn process_index(&self, pid: i32) -> Option<usize> {
+        self.processes.iter().position(|p| p.pid == pid)
+    }
+
+    pub fn add_process(&mut self, pid: i32, page_count: usize) -> bool {
+        if self.process_index(pid).is_some() {
+            return false;
+        }
+        self.processes.push(Process {
+            pid,
+            page_table: vec![PTE::default(); page_count],
+            frame_limit: 0,
+            stats: ProcessStats::default(),
+        });
+        self.rebalance();
+        true
+    }
+
+    pub fn remove_process(&mut self, pid: i32) -> Option<Process> {
+        let index = self.process_index(pid)?;
+        let process = self.processes.remove(index);
+        for pte in process.page_table.iter().filter(|pte| pte.is_valid) {
+            self.frame_pool.push(pte.frame_number);
+        }
+        self.rebalance();
+        Some(process)
+    }
+
+    // Recomputes every frame limit from the allocation scheme. Equal gives each
+    // process the same share; proportional shares by page table size. Frames
+    // left over by rounding go to the earliest processes. Manual limits are
+    // left alone.
+    pub fn rebalance(&mut self) {
+        if self.processes.is_empty() {
+            return;
+        }
+        let weights: Vec<usize> = match self.allocation {
+            FrameAllocation::Manual => return,
+            FrameAllocation::Equal => vec![1; self.processes.len()],
+            FrameAllocation::Proportional => self
+                .processes
+                .iter()
+                .map(|p| std::cmp::max(p.page_table.len(), 1))
+                .collect(),
+        };
+        let total_weight: usize = weights.iter().sum();
+        let mut limits: Vec<usize> = weights
+            .iter()
+            .map(|w| self.total_frames * w / total_weight)
+            .collect();
+        let mut remaining = self.total_frames - limits.iter().sum::<usize>();
+        for limit in limits.iter_mut() {
+            if remaining == 0 {
+                break;
+            }
+            *limit += 1;
+            remaining -= 1;
+        }
+        for (index, limit) in limits.into_iter().enumerate() {
+            let pid = self.processes[index].pid;
+            self.set_frame_limit(pid, limit);
+        }
+    }
+
+    // Sets a process's frame limit and, under local replacement, immediately
+    // returns any frames it holds beyond the new limit to the pool.
+    pub fn set_frame_limit(&mut self, pid: i32, frame_limit: usize) -> bool {
+        let index = match self.process_index(pid) {
+            Some(index) => index,
+            None => return false,
+        };
+        self.processes[index].frame_limit = frame_limit;
+        if self.scope == ReplacementScope::Local {
+            while self.processes[index].resident_count() > frame_limit {
+                match self.evict_from(&[index]) {
+                    Some((frame, _)) => self.frame_pool.push(frame),
+                    None => break,
+                }
+            }
+        }
+        true
+    }
+
+    fn evict_from(&mut self, candidates: &[usize]) -> Option<(i32, (i32, usize))> {
+        let mut owners: Vec<(usize, usize)> = Vec::new();
+        let mut ptes: Vec<PTE> = Vec::new();
+        for &index in candidates {
+            for (page_number, pte) in self.processes[index].page_table.iter().enumerate() {
+                if pte.is_valid {
+                    owners.push((index, page_number));
+                    ptes.push(*pte);
+                }
+            }
+        }
+        let victim = select_victim(self.policy, &ptes)?;
+        let (index, page_number) = owners[victim];
+        let process = &mut self.processes[index];
+        process.page_table[page_number] = PTE::default();
+        process.stats.pages_evicted += 1;
+        Some((ptes[victim].frame_number, (process.pid, page_number)))
+    }
+
+    pub fn access(&mut self, pid: i32, page_number: usize) -> PagerOutcome {
+        let index = match self.process_index(pid) {
+            Some(index) => index,
+            None => return PagerOutcome::UnknownProcess,
+        };
+        if page_number >= self.processes[index].page_table.len() {
+            return PagerOutcome::OutOfRange;
+        }
+        let current_timestamp = self.current_timestamp;
+        self.current_timestamp += 1;
+        self.processes[index].stats.accesses += 1;
+
+        let pte = &mut self.processes[index].page_table[page_number];
+        if pte.is_valid {
+            pte.last_access_timestamp = current_timestamp;
+            pte.reference_count += 1;
+            return PagerOutcome::Hit {
+                frame: pte.frame_number,
+            };
+        }
+
+        self.processes[index].stats.faults += 1;
+        let under_limit = self.scope == ReplacementScope::Global
+            || self.processes[index].resident_count() < self.processes[index].frame_limit;
+        let free_frame = if under_limit {
+            self.frame_pool.pop()
+        } else {
+            None
+        };
+        let (frame, evicted) = match free_frame {
+            Some(frame) => (frame, None),
+            None => {
+                let candidates: Vec<usize> = match self.scope {
+                    ReplacementScope::Global => (0..self.processes.len()).collect(),
+                    ReplacementScope::Local => vec![index],
+                };
+                match self.evict_from(&candidates) {
+                    Some((frame, victim)) => (frame, Some(victim)),
+                    None => return PagerOutcome::NoFrame,
+                }
+            }
+        };
+
+        self.processes[index].page_table[page_number] = PTE {
+            is_valid: true,
+            frame_number: frame,
+            arrival_timestamp: current_timestamp,
+            last_access_timestamp: current_timestamp,
+            reference_count: 1,
+            ..PTE::default()
+        };
+        PagerOutcome::Fault { frame, evicted }
+    }
+
+    pub fn run(&mut self, trace: &[(i32, usize)]) -> Vec<PagerOutcome> {
+        trace
+            .iter()
+            .map(|&(pid, page_number)| self.access(pid, page_number))
+            .collect()
+    }
+
+    pub fn total_faults(&self) -> usize {
+        self.processes.iter().map(|p| p.stats.faults).sum()
+    }
+}
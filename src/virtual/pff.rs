@@ -0,0 +1,190 @@
+use crate::r#virtual::pager::{FrameAllocation, MultiProcessPager, PagerOutcome, ReplacementScope};
+use crate::r#virtual::r#virtual::{selects_from_pte_fields, ReplacementPolicy};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+// Fault rates are measured over `window` accesses of a process. Above
+// `upper_threshold` the process is granted `step` frames, below
+// `lower_threshold` it gives `step` frames back, never going under
+// `min_frames`.
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq)]
+pub struct PffConfig {
+    pub window: usize,
+    pub upper_threshold: f64,
+    pub lower_threshold: f64,
+    pub step: usize,
+    pub min_frames: usize,
+}
+
+impl Default for PffConfig {
+    fn default() -> Self {
+        PffConfig {
+            window: 10,
+            upper_threshold: 0.5,
+            lower_threshold: 0.1,
+            step: 1,
+            min_frames: 1,
+        }
+    }
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PffAction {
+    Grant,
+    Reclaim,
+    // The process is above the upper threshold but no unallocated frames are
+    // left; a real system would start suspending processes here.
+    Denied,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq)]
+pub struct PffDecision {
+    pub timestamp: i32,
+    pub pid: i32,
+    pub fault_rate: f64,
+    pub action: PffAction,
+    pub old_limit: usize,
+    pub new_limit: usize,
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+struct PffWindow {
+    accesses: usize,
+    faults: usize,
+}
+
+// Page-fault-frequency controller over a local-replacement pager. The pager
+// is switched to manual allocation so its limits are only changed here: a
+// process added through the controller starts at `min_frames`, leaving the
+// rest of the pool free for grants, and limits are then adjusted per process
+// at the end of each window; every adjustment is kept in `trace`.
+#[derive(Debug, Clone)]
+pub struct PffController {
+    pub config: PffConfig,
+    pub pager: MultiProcessPager,
+    pub trace: Vec<PffDecision>,
+    windows: HashMap<i32, PffWindow>,
+}
+
+impl PffController {
+    pub fn new(total_frames: usize, policy: ReplacementPolicy, config: PffConfig) -> Self {
+        let pager = MultiProcessPager::new(
+            total_frames,
+            policy,
+            ReplacementScope::Local,
+            FrameAllocation::Manual,
+        );
+        PffController::from_pager(pager, config)
+    }
+
+    pub fn from_pager(mut pager: MultiProcessPager, config: PffConfig) -> Self {
+        // A policy `select_victim` cannot handle turns every fault after
+        // warm-up into NoFrame and skews the fault rates.
+        assert!(
+            selects_from_pte_fields(pager.policy),
+            "PFF supports FIFO, LRU, LFU and MRU, not {}",
+            pager.policy.name()
+        );
+        pager.scope = ReplacementScope::Local;
+        pager.allocation = FrameAllocation::Manual;
+        PffController {
+            config,
+            pager,
+            trace: Vec::new(),
+            windows: HashMap::new(),
+        }
+    }
+
+    // Starts the process at `min_frames`, or whatever is still unallocated if
+    // that is less.
+    pub fn add_process(&mut self, pid: i32, page_count: usize) -> bool {
+        if !self.pager.add_process(pid, page_count) {
+            return false;
+        }
+        let frame_limit = std::cmp::min(self.config.min_frames, self.unallocated_frames());
+        self.pager.set_frame_limit(pid, frame_limit)
+    }
+
+    pub fn remove_process(&mut self, pid: i32) -> bool {
+        self.windows.remove(&pid);
+        self.pager.remove_process(pid).is_some()
+    }
+
+    // Frames not promised to any process, available for grants.
+    pub fn unallocated_frames(&self) -> usize {
+        let allocated: usize = self.pager.processes.iter().map(|p| p.frame_limit).sum();
+        self.pager.total_frames.saturating_sub(allocated)
+    }
+
+    pub fn access(&mut self, pid: i32, page_number: usize) -> PagerOutcome {
+        let timestamp = self.pager.current_timestamp;
+        let outcome = self.pager.access(pid, page_number);
+        let window = match outcome {
+            PagerOutcome::Hit { .. } | PagerOutcome::Fault { .. } | PagerOutcome::NoFrame => {
+                self.windows.entry(pid).or_default()
+            }
+            PagerOutcome::UnknownProcess | PagerOutcome::OutOfRange => return outcome,
+        };
+        window.accesses += 1;
+        if let PagerOutcome::Fault { .. } | PagerOutcome::NoFrame = outcome {
+            window.faults += 1;
+        }
+        if window.accesses >= self.config.window {
+            let fault_rate = window.faults as f64 / window.accesses as f64;
+            *window = PffWindow::default();
+            self.adjust(pid, fault_rate, timestamp);
+        }
+        outcome
+    }
+
+    pub fn run(&mut self, trace: &[(i32, usize)]) -> Vec<PagerOutcome> {
+        trace
+            .iter()
+            .map(|&(pid, page_number)| self.access(pid, page_number))
+            .collect()
+    }
+
+    fn adjust(&mut self, pid: i32, fault_rate: f64, timestamp: i32) {
+        let old_limit = match self.pager.process(pid) {
+            Some(process) => process.frame_limit,
+            None => return,
+        };
+        let (action, new_limit) = if fault_rate > self.config.upper_threshold {
+            let grant = std::cmp::min(self.config.step, self.unallocated_frames());
+            if grant == 0 {
+                (PffAction::Denied, old_limit)
+            } else {
+                (PffAction::Grant, old_limit + grant)
+            }
+        } else if fault_rate < self.config.lower_threshold && old_limit > self.config.min_frames {
+            let new_limit = std::cmp::max(
+                old_limit.saturating_sub(self.config.step),
+                self.config.min_frames,
+            );
+            (PffAction::Reclaim, new_limit)
+        } else {
+            return;
+        };
+        self.pager.set_frame_limit(pid, new_limit);
+        self.trace.push(PffDecision {
+            timestamp,
+            pid,
+            fault_rate,
+            action,
+            old_limit,
+            new_limit,
+        });
+    }
+
+    // Frame limit of a process after each decision, starting from its
+    // limit before the first one.
+    pub fn limit_history(&self, pid: i32) -> Vec<(i32, usize)> {
+        let decisions: Vec<&PffDecision> = self.trace.iter().filter(|d| d.pid == pid).collect();
+        let mut history = Vec::new();
+        if let Some(first) = decisions.first() {
+            history.push((0, first.old_limit));
+        }
+        history.extend(decisions.iter().map(|d| (d.timestamp, d.new_limit)));
+        history
+    }
+}
@@ -0,0 +1,304 @@
+use crate::r#virtual::address::{AccessOutcome, AddressTranslator};
+use crate::r#virtual::r#virtual::ReplacementPolicy;
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+
+// Decides which pages to bring in ahead of use. `on_access` sees every
+// demand reference; `on_suspend`/`on_resume` bracket a period in which the
+// process lost all of its frames.
+pub trait Prefetcher {
+    fn name(&self) -> &'static str;
+
+    fn on_access(&mut self, page_number: usize, was_fault: bool) -> Vec<usize>;
+
+    fn on_suspend(&mut self) {}
+
+    fn on_resume(&mut self) -> Vec<usize> {
+        Vec::new()
+    }
+}
+
+// Pure demand paging, the baseline the other prefetchers are compared with.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct NoPrefetcher;
+
+impl Prefetcher for NoPrefetcher {
+    fn name(&self) -> &'static str {
+        "none"
+    }
+
+    fn on_access(&mut self, _page_number: usize, _was_fault: bool) -> Vec<usize> {
+        Vec::new()
+    }
+}
+
+// Read-ahead: a fault on page p also brings in p+1 ..= p+degree.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SequentialPrefetcher {
+    pub degree: usize,
+}
+
+impl Prefetcher for SequentialPrefetcher {
+    fn name(&self) -> &'static str {
+        "sequential"
+    }
+
+    fn on_access(&mut self, page_number: usize, was_fault: bool) -> Vec<usize> {
+        if !was_fault {
+            return Vec::new();
+        }
+        (1..=self.degree).map(|k| page_number + k).collect()
+    }
+}
+
+// Once two consecutive references are separated by the same non-zero
+// stride, prefetches the next `degree` pages along it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct StridePrefetcher {
+    pub degree: usize,
+    last_page: Option<usize>,
+    last_stride: Option<i64>,
+}
+
+impl StridePrefetcher {
+    pub fn new(degree: usize) -> Self {
+        StridePrefetcher {
+            degree,
+            last_page: None,
+            last_stride: None,
+        }
+    }
+}
+
+impl Prefetcher for StridePrefetcher {
+    fn name(&self) -> &'static str {
+        "stride"
+    }
+
+    fn on_access(&mut self, page_number: usize, _was_fault: bool) -> Vec<usize> {
+        let stride = self.last_page.map(|last| page_number as i64 - last as i64);
+        let confirmed = stride.is_some() && stride == self.last_stride && stride != Some(0);
+        self.last_page = Some(page_number);
+        self.last_stride = stride;
+        if !confirmed {
+            return Vec::new();
+        }
+        let stride = stride.unwrap_or(0);
+        (1..=self.degree as i64)
+            .map(|k| page_number as i64 + k * stride)
+            .take_while(|&page| page >= 0)
+            .map(|page| page as usize)
+            .collect()
+    }
+}
+
+// Remembers the distinct pages among the last `window` references when the
+// process is suspended and prepages them all when it resumes.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WorkingSetPrefetcher {
+    pub window: usize,
+    recent: VecDeque<usize>,
+    saved: Vec<usize>,
+}
+
+impl WorkingSetPrefetcher {
+    pub fn new(window: usize) -> Self {
+        WorkingSetPrefetcher {
+            window,
+            recent: VecDeque::new(),
+            saved: Vec::new(),
+        }
+    }
+}
+
+impl Prefetcher for WorkingSetPrefetcher {
+    fn name(&self) -> &'static str {
+        "working-set"
+    }
+
+    fn on_access(&mut self, page_number: usize, _was_fault: bool) -> Vec<usize> {
+        self.recent.push_back(page_number);
+        if self.recent.len() > self.window {
+            self.recent.pop_front();
+        }
+        Vec::new()
+    }
+
+    fn on_suspend(&mut self) {
+        self.saved.clear();
+        for &page in self.recent.iter().rev() {
+            if !self.saved.contains(&page) {
+                self.saved.push(page);
+            }
+        }
+        self.recent.clear();
+    }
+
+    fn on_resume(&mut self) -> Vec<usize> {
+        std::mem::take(&mut self.saved)
+    }
+}
+
+// A prefetch is useful when the page is referenced before it is evicted and
+// wasted when it is evicted, or the process suspended, first.
+#[derive(Deserialize, Serialize, Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct PrefetchStats {
+    pub accesses: usize,
+    pub demand_faults: usize,
+    pub prefetches_issued: usize,
+    pub useful: usize,
+    pub wasted: usize,
+}
+
+impl PrefetchStats {
+    pub fn accuracy(&self) -> f64 {
+        if self.prefetches_issued == 0 {
+            0.0
+        } else {
+            self.useful as f64 / self.prefetches_issued as f64
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct PrefetchingPager<P: Prefetcher> {
+    pub translator: AddressTranslator,
+    pub prefetcher: P,
+    pub stats: PrefetchStats,
+    frame_count: usize,
+    prefetched: Vec<bool>,
+}
+
+impl<P: Prefetcher> PrefetchingPager<P> {
+    pub fn new(
+        page_count: usize,
+        frame_count: usize,
+        policy: ReplacementPolicy,
+        prefetcher: P,
+    ) -> Self {
+        PrefetchingPager {
+            translator: AddressTranslator::new(1, page_count, frame_count, policy),
+            prefetcher,
+            stats: PrefetchStats::default(),
+            frame_count,
+            prefetched: vec![false; page_count],
+        }
+    }
+
+    fn note_eviction(&mut self, outcome: AccessOutcome) {
+        if let AccessOutcome::Fault {
+            evicted_page: Some(victim),
+            ..
+        } = outcome
+        {
+            if self.prefetched[victim] {
+                self.prefetched[victim] = false;
+                self.stats.wasted += 1;
+            }
+        }
+    }
+
+    // Prefetches go through the replacement policy like a fault does, so
+    // `limit` keeps a batch from evicting the page that triggered it.
+    fn prefetch(&mut self, pages: Vec<usize>, limit: usize) {
+        for page_number in pages.into_iter().take(limit) {
+            if page_number >= self.prefetched.len()
+                || self.translator.page_table[page_number].is_valid
+            {
+                continue;
+            }
+            let outcome = self.translator.access(page_number as u64);
+            if let AccessOutcome::Fault { .. } = outcome {
+                self.stats.prefetches_issued += 1;
+                self.prefetched[page_number] = true;
+                self.note_eviction(outcome);
+            }
+        }
+    }
+
+    pub fn access(&mut self, page_number: usize) -> AccessOutcome {
+        let outcome = self.translator.access(page_number as u64);
+        let was_fault = match outcome {
+            AccessOutcome::Hit { .. } => false,
+            AccessOutcome::Fault { .. } => true,
+            AccessOutcome::OutOfRange { .. } | AccessOutcome::NoFrame { .. } => return outcome,
+        };
+        self.stats.accesses += 1;
+        if was_fault {
+            self.stats.demand_faults += 1;
+            self.note_eviction(outcome);
+        } else if self.prefetched[page_number] {
+            self.prefetched[page_number] = false;
+            self.stats.useful += 1;
+        }
+        let pages = self.prefetcher.on_access(page_number, was_fault);
+        self.prefetch(pages, self.frame_count.saturating_sub(1));
+        outcome
+    }
+
+    pub fn run(&mut self, page_references: &[usize]) -> PrefetchStats {
+        for &page_number in page_references {
+            self.access(page_number);
+        }
+        self.stats
+    }
+
+    // Takes every frame away from the process; unused prefetches count as
+    // wasted.
+    pub fn suspend(&mut self) {
+        self.prefetcher.on_suspend();
+        for prefetched in self.prefetched.iter_mut() {
+            if *prefetched {
+                *prefetched = false;
+                self.stats.wasted += 1;
+            }
+        }
+        self.translator = AddressTranslator::new(
+            1,
+            self.prefetched.len(),
+            self.frame_count,
+            self.translator.policy,
+        );
+    }
+
+    pub fn resume(&mut self) {
+        let pages = self.prefetcher.on_resume();
+        self.prefetch(pages, self.frame_count);
+    }
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq)]
+pub struct PrefetchComparison {
+    pub baseline_faults: usize,
+    pub stats: PrefetchStats,
+}
+
+impl PrefetchComparison {
+    pub fn faults_avoided(&self) -> i64 {
+        self.baseline_faults as i64 - self.stats.demand_faults as i64
+    }
+
+    pub fn fault_reduction(&self) -> f64 {
+        if self.baseline_faults == 0 {
+            0.0
+        } else {
+            self.faults_avoided() as f64 / self.baseline_faults as f64
+        }
+    }
+}
+
+// Runs the same references with and without the prefetcher.
+pub fn compare_prefetcher<P: Prefetcher>(
+    policy: ReplacementPolicy,
+    page_references: &[usize],
+    page_count: usize,
+    frame_count: usize,
+    prefetcher: P,
+) -> PrefetchComparison {
+    let mut baseline = PrefetchingPager::new(page_count, frame_count, policy, NoPrefetcher);
+    let mut pager = PrefetchingPager::new(page_count, frame_count, policy, prefetcher);
+    PrefetchComparison {
+        baseline_faults: baseline.run(page_references).demand_faults,
+        stats: pager.run(page_references),
+    }
+}
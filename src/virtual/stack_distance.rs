@@ -0,0 +1,129 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq)]
+pub struct MissRatioPoint {
+    pub frames: usize,
+    pub faults: i32,
+    pub miss_ratio: f64,
+}
+
+// `histogram[d - 1]` counts references whose LRU stack distance is `d`; a
+// reference hits in an LRU memory of `f` frames exactly when `d <= f`.
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
+pub struct StackDistanceProfile {
+    pub reference_count: usize,
+    pub cold_misses: usize,
+    pub histogram: Vec<usize>,
+}
+
+struct FenwickTree {
+    tree: Vec<i64>,
+}
+
+impl FenwickTree {
+    fn new(size: usize) -> Self {
+        FenwickTree {
+            tree: vec![0; size + 1],
+        }
+    }
+
+    fn add(&mut self, index: usize, delta: i64) {
+        let mut i = index + 1;
+        while i < self.tree.len() {
+            self.tree[i] += delta;
+            i += i & i.wrapping_neg();
+        }
+    }
+
+    fn prefix_sum(&self, index: usize) -> i64 {
+        let mut sum = 0;
+        let mut i = index;
+        while i > 0 {
+            sum += self.tree[i];
+            i -= i & i.wrapping_neg();
+        }
+        sum
+    }
+}
+
+pub fn stack_distances(page_references: &[i32]) -> Vec<Option<usize>> {
+    let mut distances: Vec<Option<usize>> = Vec::with_capacity(page_references.len());
+    let mut last_access: HashMap<i32, usize> = HashMap::new();
+    let mut most_recent = FenwickTree::new(page_references.len());
+
+    for (position, &page_number) in page_references.iter().enumerate() {
+        match last_access.insert(page_number, position) {
+            Some(previous) => {
+                let distinct_since =
+                    most_recent.prefix_sum(position) - most_recent.prefix_sum(previous + 1);
+                distances.push(Some(distinct_since as usize + 1));
+                most_recent.add(previous, -1);
+            }
+            None => distances.push(None),
+        }
+        most_recent.add(position, 1);
+    }
+
+    distances
+}
+
+impl StackDistanceProfile {
+    pub fn from_references(page_references: &[i32]) -> Self {
+        let mut profile = StackDistanceProfile {
+            reference_count: page_references.len(),
+            cold_misses: 0,
+            histogram: Vec::new(),
+        };
+        for distance in stack_distances(page_references) {
+            match distance {
+                Some(d) => {
+                    if profile.histogram.len() < d {
+                        profile.histogram.resize(d, 0);
+                    }
+                    profile.histogram[d - 1] += 1;
+                }
+                None => profile.cold_misses += 1,
+            }
+        }
+        profile
+    }
+
+    pub fn faults_for(&self, frames: usize) -> i32 {
+        let capacity_misses: usize = self.histogram.iter().skip(frames).sum();
+        (self.cold_misses + capacity_misses) as i32
+    }
+
+    pub fn miss_ratio_curve(&self, max_frames: usize) -> Vec<MissRatioPoint> {
+        (1..=max_frames)
+            .map(|frames| {
+                let faults = self.faults_for(frames);
+                let miss_ratio = if self.reference_count == 0 {
+                    0.0
+                } else {
+                    faults as f64 / self.reference_count as f64
+                };
+                MissRatioPoint {
+                    frames,
+                    faults,
+                    miss_ratio,
+                }
+            })
+            .collect()
+    }
+}
+
+pub fn miss_ratio_curve_to_csv(curve: &[MissRatioPoint]) -> String {
+    let mut csv = String::from("frames,faults,miss_ratio\n");
+    for point in curve {
+        csv.push_str(&format!(
+            "{},{},{}\n",
+            point.frames, point.faults, point.miss_ratio
+        ));
+    }
+    csv
+}
+
+pub fn miss_ratio_curve_to_json(curve: &[MissRatioPoint]) -> serde_json::Result<String> {
+    serde_json::to_string(curve)
+}
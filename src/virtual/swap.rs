@@ -0,0 +1,160 @@
+use crate::r#virtual::address::{AccessOutcome, AddressTranslator};
+use crate::r#virtual::cost::MemoryAccess;
+use crate::r#virtual::r#virtual::ReplacementPolicy;
+use serde::{Deserialize, Serialize};
+
+// Fixed-size swap area. Each slot holds at most one page; free slots are
+// handed out lowest first.
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
+pub struct SwapDevice {
+    pub slots: Vec<Option<usize>>,
+    free_slots: Vec<usize>,
+}
+
+impl SwapDevice {
+    pub fn new(slot_count: usize) -> Self {
+        SwapDevice {
+            slots: vec![None; slot_count],
+            free_slots: (0..slot_count).rev().collect(),
+        }
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.slots.len()
+    }
+
+    pub fn used_slots(&self) -> usize {
+        self.slots.len() - self.free_slots.len()
+    }
+
+    pub fn allocate(&mut self, page_number: usize) -> Option<usize> {
+        let slot = self.free_slots.pop()?;
+        self.slots[slot] = Some(page_number);
+        Some(slot)
+    }
+
+    pub fn free(&mut self, slot: usize) -> bool {
+        match self.slots.get_mut(slot) {
+            Some(owner) if owner.is_some() => {
+                *owner = None;
+                self.free_slots.push(slot);
+                true
+            }
+            _ => false,
+        }
+    }
+}
+
+#[derive(Deserialize, Serialize, Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct SwapStats {
+    pub swap_outs: usize,
+    pub swap_ins: usize,
+    pub zero_fill_faults: usize,
+    pub writes_avoided: usize,
+    pub slot_allocations: usize,
+    pub slot_frees: usize,
+    pub peak_slots_used: usize,
+    pub out_of_swap: usize,
+}
+
+// Demand pager that backs evicted pages with a swap device. A dirty victim
+// is written to its slot, allocating one if it has none; a clean victim that
+// still has a slot is in the swap cache and is dropped without I/O. A page
+// keeps its slot after being swapped in, so only writes make the copy stale.
+// When swap is full a dirty victim is lost and counted in `out_of_swap`.
+#[derive(Debug, Clone)]
+pub struct SwappingPager {
+    pub translator: AddressTranslator,
+    pub swap: SwapDevice,
+    pub slot_of: Vec<Option<usize>>,
+    pub dirty: Vec<bool>,
+    pub stats: SwapStats,
+}
+
+impl SwappingPager {
+    pub fn new(
+        page_count: usize,
+        frame_count: usize,
+        slot_count: usize,
+        policy: ReplacementPolicy,
+    ) -> Self {
+        SwappingPager {
+            translator: AddressTranslator::new(1, page_count, frame_count, policy),
+            swap: SwapDevice::new(slot_count),
+            slot_of: vec![None; page_count],
+            dirty: vec![false; page_count],
+            stats: SwapStats::default(),
+        }
+    }
+
+    fn swap_out(&mut self, page_number: usize) {
+        if !self.dirty[page_number] {
+            if self.slot_of[page_number].is_some() {
+                self.stats.writes_avoided += 1;
+            }
+            return;
+        }
+        self.dirty[page_number] = false;
+        if self.slot_of[page_number].is_none() {
+            match self.swap.allocate(page_number) {
+                Some(slot) => {
+                    self.slot_of[page_number] = Some(slot);
+                    self.stats.slot_allocations += 1;
+                    self.stats.peak_slots_used =
+                        std::cmp::max(self.stats.peak_slots_used, self.swap.used_slots());
+                }
+                None => {
+                    self.stats.out_of_swap += 1;
+                    return;
+                }
+            }
+        }
+        self.stats.swap_outs += 1;
+    }
+
+    pub fn access(&mut self, access: MemoryAccess) -> AccessOutcome {
+        let page_number = access.page_number;
+        let outcome = self.translator.access(page_number as u64);
+        match outcome {
+            AccessOutcome::Hit { .. } => {}
+            AccessOutcome::Fault { evicted_page, .. } => {
+                if let Some(victim) = evicted_page {
+                    self.swap_out(victim);
+                }
+                if self.slot_of[page_number].is_some() {
+                    self.stats.swap_ins += 1;
+                } else {
+                    self.stats.zero_fill_faults += 1;
+                }
+            }
+            AccessOutcome::OutOfRange { .. } | AccessOutcome::NoFrame { .. } => return outcome,
+        }
+        if access.is_write {
+            self.dirty[page_number] = true;
+        }
+        outcome
+    }
+
+    pub fn run(&mut self, accesses: &[MemoryAccess]) -> SwapStats {
+        for &access in accesses {
+            self.access(access);
+        }
+        self.stats
+    }
+
+    // Releases the page's slot, e.g. when its contents are no longer needed.
+    pub fn free_slot(&mut self, page_number: usize) -> bool {
+        match self
+            .slot_of
+            .get_mut(page_number)
+            .and_then(|slot| slot.take())
+        {
+            Some(slot) => {
+                self.swap.free(slot);
+                self.stats.slot_frees += 1;
+                true
+            }
+            None => false,
+        }
+    }
+}
@@ -0,0 +1,140 @@
+#[cfg(test)]
+
+mod test_address {
+    use crate::r#virtual::address::{AccessOutcome, AddressTranslator};
+    use crate::r#virtual::r#virtual::{AgingConfig, ReplacementPolicy, PTE};
+
+    #[test]
+    pub fn test_split() {
+        let translator = AddressTranslator::new(4096, 8, 2, ReplacementPolicy::Lru);
+
+        assert_eq!(translator.split(0x3abc), (3, 0xabc));
+        assert_eq!(translator.split(0x0fff), (0, 0xfff));
+    }
+
+    #[test]
+    pub fn test_split_page_size_not_power_of_two() {
+        let translator = AddressTranslator::new(1000, 8, 2, ReplacementPolicy::Fifo);
+
+        assert_eq!(translator.split(2500), (2, 500));
+    }
+
+    #[test]
+    pub fn test_access() {
+        let mut translator = AddressTranslator::new(4096, 8, 2, ReplacementPolicy::Lru);
+
+        let outcomes = translator.access_all(vec![0x0010, 0x1020, 0x0030, 0x2000, 0x8000]);
+
+        assert_eq!(
+            outcomes,
+            vec![
+                AccessOutcome::Fault {
+                    page_number: 0,
+                    physical_address: 0x0010,
+                    evicted_page: None
+                },
+                AccessOutcome::Fault {
+                    page_number: 1,
+                    physical_address: 0x1020,
+                    evicted_page: None
+                },
+                AccessOutcome::Hit {
+                    page_number: 0,
+                    physical_address: 0x0030
+                },
+                AccessOutcome::Fault {
+                    page_number: 2,
+                    physical_address: 0x1000,
+                    evicted_page: Some(1)
+                },
+                AccessOutcome::OutOfRange {
+                    virtual_address: 0x8000
+                },
+            ]
+        );
+        assert_eq!(outcomes[2].physical_address(), Some(0x30));
+        assert_eq!(outcomes[4].physical_address(), None);
+        assert_eq!(translator.hits, 1);
+        assert_eq!(translator.faults, 3);
+    }
+
+    #[test]
+    pub fn test_access_preloaded_page_table() {
+        let mut page_table: Vec<PTE> = vec![PTE::default(); 8];
+        page_table[2] = PTE {
+            is_valid: true,
+            frame_number: 10,
+            arrival_timestamp: 3,
+            last_access_timestamp: 3,
+            reference_count: 1,
+            ..PTE::default()
+        };
+        page_table[5] = PTE {
+            is_valid: true,
+            frame_number: 20,
+            arrival_timestamp: 2,
+            last_access_timestamp: 4,
+            reference_count: 2,
+            ..PTE::default()
+        };
+        let mut translator =
+            AddressTranslator::from_parts(256, page_table, Vec::new(), ReplacementPolicy::Fifo);
+
+        assert_eq!(
+            translator.access(0x205),
+            AccessOutcome::Hit {
+                page_number: 2,
+                physical_address: 10 * 256 + 5
+            }
+        );
+        assert_eq!(
+            translator.access(0x001),
+            AccessOutcome::Fault {
+                page_number: 0,
+                physical_address: 20 * 256 + 1,
+                evicted_page: Some(5)
+            }
+        );
+        assert_eq!(translator.current_timestamp, 6);
+    }
+
+    #[test]
+    pub fn test_access_no_frame() {
+        let mut translator = AddressTranslator::new(4096, 8, 0, ReplacementPolicy::Lru);
+
+        assert_eq!(
+            translator.access(0x10),
+            AccessOutcome::NoFrame { page_number: 0 }
+        );
+    }
+
+    #[test]
+    pub fn test_access_faults_match_count_page_faults() {
+        let reference_string = vec![0, 3, 2, 6, 3, 4, 5, 2, 4, 5, 6, 0, 3, 2, 1, 7];
+        let policies = [
+            ReplacementPolicy::Fifo,
+            ReplacementPolicy::Lru,
+            ReplacementPolicy::Lfu,
+            ReplacementPolicy::Mru,
+            ReplacementPolicy::Random(3),
+            ReplacementPolicy::Clock,
+            ReplacementPolicy::ClockPro,
+            ReplacementPolicy::Aging(AgingConfig::default()),
+            ReplacementPolicy::Arc,
+            ReplacementPolicy::Lirs,
+            ReplacementPolicy::TwoQ,
+        ];
+
+        for policy in policies.iter() {
+            let mut translator = AddressTranslator::new(512, 8, 3, *policy);
+            let addresses = reference_string.iter().map(|&p| p as u64 * 512 + 7);
+
+            translator.access_all(addresses);
+
+            assert_eq!(
+                translator.faults as i32,
+                policy.count_page_faults_for_frames(&reference_string, 3)
+            );
+        }
+    }
+}
@@ -0,0 +1,75 @@
+#[cfg(test)]
+
+mod test_belady {
+    use crate::r#virtual::belady::{
+        detect_belady_anomalies, find_fifo_belady_anomaly, sweep_frame_counts, BeladyAnomaly,
+    };
+    use crate::r#virtual::r#virtual::ReplacementPolicy;
+
+    fn belady_reference_string() -> Vec<i32> {
+        vec![1, 2, 3, 4, 1, 2, 5, 1, 2, 3, 4, 5]
+    }
+
+    #[test]
+    pub fn test_sweep_frame_counts() {
+        let sweep = sweep_frame_counts(ReplacementPolicy::Fifo, &belady_reference_string(), 1, 5);
+
+        assert_eq!(sweep, vec![(1, 12), (2, 12), (3, 9), (4, 10), (5, 5)]);
+    }
+
+    #[test]
+    pub fn test_detect_belady_anomalies_fifo() {
+        let anomalies =
+            detect_belady_anomalies(ReplacementPolicy::Fifo, &belady_reference_string(), 1, 6);
+
+        assert_eq!(
+            anomalies,
+            vec![BeladyAnomaly {
+                frames: 3,
+                faults: 9,
+                next_faults: 10
+            }]
+        );
+    }
+
+    #[test]
+    pub fn test_detect_belady_anomalies_lru() {
+        let anomalies =
+            detect_belady_anomalies(ReplacementPolicy::Lru, &belady_reference_string(), 1, 6);
+
+        assert_eq!(anomalies, Vec::new());
+    }
+
+    #[test]
+    pub fn test_detect_belady_anomalies_clock() {
+        let anomalies =
+            detect_belady_anomalies(ReplacementPolicy::Clock, &belady_reference_string(), 1, 6);
+
+        assert_eq!(anomalies.len(), 1);
+        assert_eq!(anomalies[0].frames, 3);
+    }
+
+    #[test]
+    pub fn test_find_fifo_belady_anomaly() {
+        let example = find_fifo_belady_anomaly(5, 20, 5, 0, 1000).unwrap();
+
+        assert_eq!(example.reference_string.len(), 12);
+        assert!(example.anomaly.next_faults > example.anomaly.faults);
+        assert_eq!(
+            detect_belady_anomalies(ReplacementPolicy::Fifo, &example.reference_string, 1, 5)[0],
+            example.anomaly
+        );
+    }
+
+    #[test]
+    pub fn test_find_fifo_belady_anomaly_gives_up() {
+        let example = find_fifo_belady_anomaly(2, 8, 3, 0, 100);
+
+        assert_eq!(example, None);
+    }
+
+    #[test]
+    pub fn test_find_fifo_belady_anomaly_without_pages() {
+        assert_eq!(find_fifo_belady_anomaly(0, 8, 3, 0, 100), None);
+    }
+}
@@ -0,0 +1,101 @@
+#[cfg(test)]
+
+mod test_checkpoint {
+    use crate::r#virtual::address::AddressTranslator;
+    use crate::r#virtual::checkpoint::{
+        checkpoint_to_json, diff_pagers, load_checkpoint, resume_from_json, save_checkpoint,
+    };
+    use crate::r#virtual::r#virtual::{AgingConfig, ReplacementPolicy, PTE};
+
+    #[test]
+    pub fn test_pte_round_trip() {
+        let pte = PTE {
+            is_valid: true,
+            frame_number: 3,
+            arrival_timestamp: 4,
+            last_access_timestamp: 9,
+            reference_count: 2,
+            is_dirty: true,
+            ..PTE::default()
+        };
+
+        let json = serde_json::to_string(&pte).unwrap();
+
+        assert_eq!(serde_json::from_str::<PTE>(&json).unwrap(), pte);
+    }
+
+    #[test]
+    pub fn test_resume_matches_uninterrupted_run() {
+        let references: Vec<u64> = vec![0, 3, 2, 6, 3, 4, 5, 2, 4, 5, 6, 1, 0, 3, 1, 6, 2];
+        let policies = [
+            ReplacementPolicy::Fifo,
+            ReplacementPolicy::Lru,
+            ReplacementPolicy::Lfu,
+            ReplacementPolicy::Mru,
+            ReplacementPolicy::Random(7),
+            ReplacementPolicy::Clock,
+            ReplacementPolicy::ClockPro,
+            ReplacementPolicy::Aging(AgingConfig::default()),
+            ReplacementPolicy::Arc,
+            ReplacementPolicy::Lirs,
+            ReplacementPolicy::TwoQ,
+        ];
+
+        for &policy in policies.iter() {
+            let mut uninterrupted = AddressTranslator::new(1, 8, 3, policy);
+            let expected = uninterrupted.access_all(references.iter().copied());
+
+            let mut first = AddressTranslator::new(1, 8, 3, policy);
+            let mut outcomes = first.access_all(references[..8].iter().copied());
+            let json = checkpoint_to_json(&first).unwrap();
+            let mut resumed = resume_from_json(&json).unwrap();
+            assert_eq!(resumed, first, "{}", policy.name());
+            outcomes.extend(resumed.access_all(references[8..].iter().copied()));
+
+            assert_eq!(outcomes, expected, "{}", policy.name());
+            assert_eq!(resumed, uninterrupted, "{}", policy.name());
+        }
+    }
+
+    #[test]
+    pub fn test_save_and_load_checkpoint() {
+        let mut translator = AddressTranslator::new(256, 8, 3, ReplacementPolicy::Arc);
+        translator.access_all(vec![0x000, 0x100, 0x305, 0x000]);
+        let path = std::env::temp_dir().join("mos_rust_test_checkpoint.json");
+
+        save_checkpoint(&translator, &path).unwrap();
+        let loaded = load_checkpoint(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        assert_eq!(loaded, translator);
+        assert!(load_checkpoint(&path).is_err());
+    }
+
+    #[test]
+    pub fn test_diff_checkpoints() {
+        let mut translator = AddressTranslator::new(1, 8, 3, ReplacementPolicy::Lru);
+        translator.access_all(vec![0, 1, 2]);
+        let before = translator.clone();
+        translator.access(3);
+
+        let diff = diff_pagers(&before, &translator);
+
+        assert_eq!(diff.page_changes.len(), 2);
+        assert!(diff.page_changes[0].unmapped());
+        assert_eq!(diff.page_changes[0].page_number, 0);
+        assert!(diff.page_changes[1].mapped());
+        assert_eq!(diff.page_changes[1].after.frame_number, 0);
+        assert!(diff.frames_taken.is_empty());
+        assert_eq!(diff.timestamp_delta, 1);
+        assert_eq!(diff.faults_delta, 1);
+        assert!(!diff.policy_state_changed);
+        assert!(diff_pagers(&translator, &translator).is_empty());
+
+        let mut clock = AddressTranslator::new(1, 8, 3, ReplacementPolicy::Clock);
+        let start = clock.clone();
+        clock.access(5);
+        let diff = diff_pagers(&start, &clock);
+        assert_eq!(diff.frames_taken, vec![0]);
+        assert!(diff.policy_state_changed);
+    }
+}
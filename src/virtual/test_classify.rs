@@ -0,0 +1,125 @@
+#[cfg(test)]
+
+mod test_classify {
+    use crate::r#virtual::classify::{classify_faults, FaultClass};
+    use crate::r#virtual::r#virtual::ReplacementPolicy;
+    use crate::r#virtual::workload::{generate_workload, Workload};
+
+    #[test]
+    pub fn test_lfu_breakdown() {
+        let reference_string = [0, 3, 2, 6, 3, 4, 5, 2, 4, 5, 6];
+
+        let (faults, breakdown) = classify_faults(ReplacementPolicy::Lfu, &reference_string, 3);
+
+        assert_eq!(breakdown.faults, 10);
+        assert_eq!(breakdown.compulsory, 6);
+        assert_eq!(breakdown.capacity, 2);
+        assert_eq!(breakdown.policy_induced, 2);
+        assert_eq!(breakdown.opt_faults, 7);
+        assert_eq!(breakdown.avoidable_by_opt, 3);
+        assert_eq!(breakdown.excess_over_opt(), 3);
+        assert_eq!(faults[7].index, 8);
+        assert_eq!(faults[7].class, FaultClass::PolicyInduced);
+        assert!(!faults[7].opt_faults);
+        assert_eq!(faults[9].class, FaultClass::Capacity);
+        assert!(faults[9].opt_faults);
+    }
+
+    #[test]
+    pub fn test_lru_faults_avoidable_by_opt_are_capacity() {
+        let reference_string = [7, 0, 1, 2, 0, 3, 0, 4, 2, 3, 0, 3, 2, 1, 2, 0, 1, 7, 0, 1];
+
+        let (faults, breakdown) = classify_faults(ReplacementPolicy::Lru, &reference_string, 3);
+
+        assert_eq!(breakdown.faults, 12);
+        assert_eq!(breakdown.opt_faults, 9);
+        assert_eq!(breakdown.compulsory, 6);
+        assert_eq!(breakdown.capacity, 6);
+        assert_eq!(breakdown.policy_induced, 0);
+        assert_eq!(breakdown.avoidable_by_opt, 3);
+        let avoidable: Vec<(usize, FaultClass)> = faults
+            .iter()
+            .filter(|fault| !fault.opt_faults)
+            .map(|fault| (fault.index, fault.class))
+            .collect();
+        assert_eq!(
+            avoidable,
+            vec![
+                (8, FaultClass::Capacity),
+                (9, FaultClass::Capacity),
+                (15, FaultClass::Capacity)
+            ]
+        );
+    }
+
+    #[test]
+    pub fn test_policy_induced_faults_are_opt_hits() {
+        let reference_string = generate_workload(
+            &Workload::Zipf {
+                page_count: 40,
+                exponent: 0.8,
+            },
+            2000,
+            11,
+        );
+
+        for &policy in [
+            ReplacementPolicy::Lru,
+            ReplacementPolicy::Fifo,
+            ReplacementPolicy::Lfu,
+            ReplacementPolicy::Mru,
+            ReplacementPolicy::Clock,
+        ]
+        .iter()
+        {
+            for frames in [2, 5, 10, 20].iter() {
+                let (faults, breakdown) = classify_faults(policy, &reference_string, *frames);
+                assert!(faults
+                    .iter()
+                    .filter(|fault| fault.class == FaultClass::PolicyInduced)
+                    .all(|fault| !fault.opt_faults));
+                assert!(breakdown.avoidable_by_opt >= breakdown.policy_induced);
+                assert!(breakdown.compulsory <= 40);
+                if policy == ReplacementPolicy::Lru {
+                    assert_eq!(breakdown.policy_induced, 0);
+                }
+            }
+        }
+    }
+
+    #[test]
+    pub fn test_breakdown_sums_to_fault_count() {
+        let reference_string = generate_workload(
+            &Workload::PhaseChange {
+                page_count: 64,
+                working_set_size: 6,
+                phase_length: 200,
+                locality: 0.9,
+            },
+            1000,
+            5,
+        );
+
+        for &policy in [
+            ReplacementPolicy::Fifo,
+            ReplacementPolicy::Lfu,
+            ReplacementPolicy::Mru,
+            ReplacementPolicy::Clock,
+            ReplacementPolicy::Arc,
+        ]
+        .iter()
+        {
+            let (faults, breakdown) = classify_faults(policy, &reference_string, 8);
+            assert_eq!(
+                breakdown.faults as i32,
+                policy.count_page_faults_for_frames(&reference_string, 8)
+            );
+            assert_eq!(faults.len(), breakdown.faults);
+            assert_eq!(
+                breakdown.faults,
+                breakdown.compulsory + breakdown.capacity + breakdown.policy_induced
+            );
+            assert!(breakdown.avoidable_by_opt as i64 >= breakdown.excess_over_opt());
+        }
+    }
+}
@@ -0,0 +1,132 @@
+#[cfg(test)]
+
+mod test_compare {
+    use crate::r#virtual::compare::{run_comparison, ComparisonTable, NamedTrace};
+    use crate::r#virtual::r#virtual::{
+        count_page_faults_fifo, count_page_faults_lfu, count_page_faults_lru, AgingConfig,
+        ReplacementPolicy, PTE,
+    };
+
+    fn standard_traces() -> Vec<NamedTrace> {
+        vec![
+            NamedTrace::new("case1", vec![0, 3, 2, 6, 3, 4, 5, 2, 4, 5, 6]),
+            NamedTrace::new("case2", vec![2, 5, 0, 2, 5, 2, 3, 5, 1, 2, 6, 0]),
+        ]
+    }
+
+    #[test]
+    pub fn test_comparison_matches_count_page_faults() {
+        let policies = [
+            ReplacementPolicy::Fifo,
+            ReplacementPolicy::Lru,
+            ReplacementPolicy::Lfu,
+        ];
+        let frame_counts = [1, 2, 3, 4];
+
+        let table = run_comparison(&standard_traces(), &policies, &frame_counts, 4);
+
+        assert_eq!(table.rows.len(), 24);
+        assert_eq!(table.rows[5].trace, "case1");
+        assert_eq!(table.rows[5].policy, ReplacementPolicy::Lru);
+        assert_eq!(table.rows[5].frames, 2);
+        for trace in standard_traces() {
+            for &frames in frame_counts.iter() {
+                let counts = [
+                    count_page_faults_fifo as fn(&mut Vec<PTE>, Vec<i32>, &mut Vec<i32>) -> i32,
+                    count_page_faults_lru,
+                    count_page_faults_lfu,
+                ];
+                for (policy, count) in policies.iter().zip(counts.iter()) {
+                    let mut page_table = vec![PTE::default(); 7];
+                    let mut frame_pool: Vec<i32> = (0..frames as i32).rev().collect();
+                    let faults = count(
+                        &mut page_table,
+                        trace.page_references.clone(),
+                        &mut frame_pool,
+                    );
+                    assert_eq!(table.faults(&trace.name, *policy, frames), Some(faults));
+                }
+            }
+        }
+    }
+
+    #[test]
+    pub fn test_thread_count_does_not_change_results() {
+        let policies = [
+            ReplacementPolicy::Clock,
+            ReplacementPolicy::Arc,
+            ReplacementPolicy::Random(3),
+        ];
+
+        let serial = run_comparison(&standard_traces(), &policies, &[2, 3, 5], 1);
+        let parallel = run_comparison(&standard_traces(), &policies, &[2, 3, 5], 8);
+
+        assert_eq!(serial, parallel);
+    }
+
+    #[test]
+    pub fn test_markdown_table() {
+        let traces = vec![NamedTrace::new(
+            "case1",
+            vec![0, 3, 2, 6, 3, 4, 5, 2, 4, 5, 6],
+        )];
+
+        let table = run_comparison(
+            &traces,
+            &[ReplacementPolicy::Fifo, ReplacementPolicy::Lru],
+            &[3, 4],
+            2,
+        );
+
+        assert_eq!(
+            table.to_markdown(),
+            "| Trace | Policy | 3 frames | 4 frames |\n\
+             |---|---|---:|---:|\n\
+             | case1 | FIFO | 8 | 6 |\n\
+             | case1 | LRU | 8 | 8 |\n"
+        );
+    }
+
+    #[test]
+    pub fn test_labels_tell_parameterised_policies_apart() {
+        let traces = vec![NamedTrace::new("t", vec![0, 1, 2, 0, 3, 1, 2, 4, 0, 3])];
+        let policies = [
+            ReplacementPolicy::Random(1),
+            ReplacementPolicy::Random(2),
+            ReplacementPolicy::Aging(AgingConfig {
+                counter_bits: 4,
+                tick_interval: 2,
+            }),
+        ];
+
+        let table = run_comparison(&traces, &policies, &[2], 2);
+
+        let markdown = table.to_markdown();
+        assert!(markdown.contains("| t | Random(seed=1) |"));
+        assert!(markdown.contains("| t | Random(seed=2) |"));
+        assert!(markdown.contains("| t | Aging(bits=4, tick=2) |"));
+        let csv = table.to_csv();
+        assert!(csv.contains("\nt,Random(seed=1),2,10,"));
+        assert!(csv.contains("\nt,Random(seed=2),2,10,"));
+        assert!(csv.contains("\nt,\"Aging(bits=4, tick=2)\",2,10,"));
+    }
+
+    #[test]
+    pub fn test_csv_and_json() {
+        let traces = vec![NamedTrace::new("loop, small", vec![0, 1, 0, 1])];
+
+        let table = run_comparison(&traces, &[ReplacementPolicy::Lru], &[1, 2], 2);
+
+        assert_eq!(
+            table.to_csv(),
+            "trace,policy,frames,references,faults,fault_rate\n\
+             \"loop, small\",LRU,1,4,4,1\n\
+             \"loop, small\",LRU,2,4,2,0.5\n"
+        );
+        let json = table.to_json().unwrap();
+        assert_eq!(
+            serde_json::from_str::<ComparisonTable>(&json).unwrap(),
+            table
+        );
+    }
+}
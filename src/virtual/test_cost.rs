@@ -0,0 +1,93 @@
+#[cfg(test)]
+
+mod test_cost {
+    use crate::r#virtual::cost::{
+        simulate_cost, simulate_cost_for_references, CostModel, MemoryAccess,
+    };
+    use crate::r#virtual::r#virtual::ReplacementPolicy;
+
+    fn small_model(disk_queue: bool) -> CostModel {
+        CostModel {
+            memory_access_ns: 100.0,
+            fault_service_ns: 1000.0,
+            write_back_ns: 1000.0,
+            disk_queue,
+        }
+    }
+
+    #[test]
+    pub fn test_read_only_run() {
+        let references = [0, 3, 2, 6, 3, 4, 5, 2, 4, 5, 6];
+        let model = CostModel::default();
+
+        let report = simulate_cost_for_references(ReplacementPolicy::Lru, &references, 3, model);
+
+        assert_eq!(report.accesses, 11);
+        assert_eq!(report.faults, 8);
+        assert_eq!(report.write_backs, 0);
+        assert_eq!(report.total_time_ns, 11.0 * 100.0 + 8.0 * 8_000_000.0);
+        assert_eq!(
+            report.effective_access_time(),
+            model.effective_access_time(report.fault_rate(), 0.0)
+        );
+    }
+
+    #[test]
+    pub fn test_dirty_write_back() {
+        let accesses = [
+            MemoryAccess::write(0),
+            MemoryAccess::read(1),
+            MemoryAccess::read(0),
+            MemoryAccess::read(1),
+        ];
+
+        let report = simulate_cost(ReplacementPolicy::Fifo, 2, 1, &accesses, small_model(false));
+
+        assert_eq!(report.faults, 4);
+        assert_eq!(report.write_backs, 1);
+        assert_eq!(report.write_back_time_ns, 1000.0);
+        assert_eq!(report.total_time_ns, 4.0 * 100.0 + 4.0 * 1000.0 + 1000.0);
+    }
+
+    #[test]
+    pub fn test_disk_queue_overlaps_write_back() {
+        let accesses = [
+            MemoryAccess::write(0),
+            MemoryAccess::read(1),
+            MemoryAccess::read(1),
+            MemoryAccess::read(0),
+        ];
+
+        let sync = simulate_cost(ReplacementPolicy::Lru, 2, 1, &accesses, small_model(false));
+        let queued = simulate_cost(ReplacementPolicy::Lru, 2, 1, &accesses, small_model(true));
+
+        assert_eq!(sync.total_time_ns, 4400.0);
+        assert_eq!(sync.queue_wait_ns, 0.0);
+        assert_eq!(queued.total_time_ns, 4200.0);
+        assert_eq!(queued.queue_wait_ns, 800.0);
+        assert_eq!(queued.write_backs, sync.write_backs);
+    }
+
+    #[test]
+    pub fn test_fault_difference_as_time() {
+        let references = [0, 3, 2, 6, 3, 4, 5, 2, 4, 5, 6];
+        let model = CostModel::default();
+
+        let fifo = simulate_cost_for_references(ReplacementPolicy::Fifo, &references, 3, model);
+        let lfu = simulate_cost_for_references(ReplacementPolicy::Lfu, &references, 3, model);
+
+        assert_eq!(lfu.faults - fifo.faults, 2);
+        assert_eq!(
+            lfu.total_time_ns - fifo.total_time_ns,
+            2.0 * model.fault_service_ns
+        );
+    }
+
+    #[test]
+    pub fn test_analytic_effective_access_time() {
+        let model = small_model(false);
+
+        assert_eq!(model.effective_access_time(0.0, 0.0), 100.0);
+        assert_eq!(model.effective_access_time(0.5, 0.5), 50.0 + 0.5 * 1600.0);
+    }
+}
@@ -0,0 +1,93 @@
+#[cfg(test)]
+
+mod test_cow {
+    use crate::r#virtual::cow::{CowMemory, WriteOutcome};
+
+    fn forked_memory() -> CowMemory {
+        let mut memory = CowMemory::new(8);
+        memory.create_process(1, 4);
+        memory.map_page(1, 0, false);
+        memory.map_page(1, 1, false);
+        memory.map_page(1, 2, true);
+        memory.fork(1, 2);
+        memory
+    }
+
+    #[test]
+    pub fn test_fork_shares_frames() {
+        let memory = forked_memory();
+
+        assert_eq!(memory.frame_pool.len(), 5);
+        assert_eq!(memory.shared_frames(), 3);
+        assert_eq!(memory.stats.pages_shared, 2);
+        let child = &memory.page_tables[&2];
+        assert!(child[0].is_cow);
+        assert!(!child[2].is_cow);
+        assert!(child[2].is_read_only);
+        assert!(memory.page_tables[&1][1].is_cow);
+        assert_eq!(
+            child[0].frame_number,
+            memory.page_tables[&1][0].frame_number
+        );
+        assert_eq!(memory.refcount(child[0].frame_number), 2);
+    }
+
+    #[test]
+    pub fn test_write_copies_shared_frame() {
+        let mut memory = forked_memory();
+
+        assert_eq!(
+            memory.write(2, 0),
+            WriteOutcome::Copied {
+                old_frame: 0,
+                new_frame: 3
+            }
+        );
+        assert_eq!(memory.refcount(0), 1);
+        assert_eq!(memory.refcount(3), 1);
+        assert_eq!(memory.read(1, 0), Some(0));
+        assert_eq!(memory.read(2, 0), Some(3));
+
+        assert_eq!(memory.write(1, 0), WriteOutcome::Written { frame: 0 });
+        assert!(!memory.page_tables[&1][0].is_cow);
+        assert_eq!(memory.stats.cow_faults, 2);
+        assert_eq!(memory.stats.copies, 1);
+        assert_eq!(memory.stats.copies_avoided(), 1);
+    }
+
+    #[test]
+    pub fn test_write_protection() {
+        let mut memory = forked_memory();
+
+        assert_eq!(memory.write(1, 2), WriteOutcome::ProtectionFault);
+        assert_eq!(memory.write(1, 3), WriteOutcome::NotMapped);
+        assert_eq!(memory.write(9, 0), WriteOutcome::UnknownProcess);
+        assert_eq!(memory.stats.protection_faults, 1);
+    }
+
+    #[test]
+    pub fn test_exit_releases_frames() {
+        let mut memory = forked_memory();
+        memory.write(2, 0);
+
+        assert!(memory.exit(2));
+        assert_eq!(memory.frame_pool.len(), 5);
+        assert_eq!(memory.shared_frames(), 0);
+        assert_eq!(memory.write(1, 1), WriteOutcome::Written { frame: 1 });
+        assert_eq!(memory.stats.copies_avoided(), 1);
+
+        assert!(memory.exit(1));
+        assert_eq!(memory.frame_pool.len(), 8);
+    }
+
+    #[test]
+    pub fn test_copy_out_of_memory() {
+        let mut memory = CowMemory::new(1);
+        memory.create_process(1, 1);
+        memory.map_page(1, 0, false);
+        memory.fork(1, 2);
+
+        assert_eq!(memory.write(1, 0), WriteOutcome::OutOfMemory);
+        assert!(memory.page_tables[&1][0].is_cow);
+    }
+}
@@ -0,0 +1,156 @@
+#[cfg(test)]
+
+mod test_events {
+    use crate::r#virtual::events::{
+        fault_events, fault_events_fifo, fault_events_lfu, fault_events_lru, summarize_events,
+        EventKind, PageEvent,
+    };
+    use crate::r#virtual::r#virtual::{
+        count_page_faults_fifo, count_page_faults_lfu, count_page_faults_lru, ReplacementPolicy,
+        PTE,
+    };
+    use std::cell::Cell;
+
+    #[test]
+    pub fn test_event_stream() {
+        let references = vec![0, 3, 2, 6, 3, 4, 5, 2, 4, 5, 6];
+
+        let events: Vec<PageEvent> =
+            fault_events_lru(vec![PTE::default(); 7], vec![2, 1, 0], references).collect();
+
+        let kinds: Vec<EventKind> = events.iter().map(|e| e.kind).collect();
+        assert_eq!(
+            kinds,
+            vec![
+                EventKind::ColdMiss,
+                EventKind::ColdMiss,
+                EventKind::ColdMiss,
+                EventKind::ColdMiss,
+                EventKind::Hit,
+                EventKind::ColdMiss,
+                EventKind::ColdMiss,
+                EventKind::CapacityMiss,
+                EventKind::Hit,
+                EventKind::Hit,
+                EventKind::CapacityMiss,
+            ]
+        );
+        assert_eq!(events[0].frame, 0);
+        assert_eq!(events[3].evicted_page, Some(0));
+        assert_eq!(events[3].frame, 0);
+        assert_eq!(events[7].evicted_page, Some(3));
+        assert_eq!(events[10].index, 10);
+    }
+
+    #[test]
+    pub fn test_event_frames_match_page_table() {
+        let mut events = fault_events_fifo(
+            vec![PTE::default(); 8],
+            vec![3, 2, 1, 0],
+            vec![1, 2, 3, 4, 1, 2, 5, 1, 2, 3, 4, 5],
+        );
+
+        while let Some(event) = events.next() {
+            let pte = events.translator().page_table[event.page_number as usize];
+            assert!(pte.is_valid);
+            assert_eq!(event.frame, pte.frame_number);
+        }
+    }
+
+    #[test]
+    pub fn test_summary_matches_count_page_faults() {
+        let reference_strings = [
+            vec![0, 3, 2, 6, 3, 4, 5, 2, 4, 5, 6],
+            vec![2, 5, 0, 2, 5, 2, 3, 5, 1, 2, 6, 0],
+            vec![1, 2, 3, 4, 1, 2, 5, 1, 2, 3, 4, 5],
+        ];
+
+        for references in reference_strings.iter() {
+            let mut page_table = vec![PTE::default(); 7];
+            let mut frame_pool = vec![0, 1, 2];
+            let fifo = count_page_faults_fifo(&mut page_table, references.clone(), &mut frame_pool);
+            let summary = summarize_events(fault_events_fifo(
+                vec![PTE::default(); 7],
+                vec![0, 1, 2],
+                references.clone(),
+            ));
+            assert_eq!(summary.faults() as i32, fifo);
+
+            let mut page_table = vec![PTE::default(); 7];
+            let mut frame_pool = vec![0, 1, 2];
+            let lru = count_page_faults_lru(&mut page_table, references.clone(), &mut frame_pool);
+            let summary = summarize_events(fault_events_lru(
+                vec![PTE::default(); 7],
+                vec![0, 1, 2],
+                references.clone(),
+            ));
+            assert_eq!(summary.faults() as i32, lru);
+
+            let mut page_table = vec![PTE::default(); 7];
+            let mut frame_pool = vec![0, 1, 2];
+            let lfu = count_page_faults_lfu(&mut page_table, references.clone(), &mut frame_pool);
+            let summary = summarize_events(fault_events_lfu(
+                vec![PTE::default(); 7],
+                vec![0, 1, 2],
+                references.clone(),
+            ));
+            assert_eq!(summary.faults() as i32, lfu);
+            assert_eq!(summary.accesses, references.len());
+            assert_eq!(summary.evictions, summary.faults() - 3);
+        }
+    }
+
+    #[test]
+    pub fn test_unbounded_source_is_consumed_lazily() {
+        let pulled = Cell::new(0);
+        let source = (0..).map(|i| {
+            pulled.set(pulled.get() + 1);
+            i % 4
+        });
+
+        let mut events = fault_events(
+            ReplacementPolicy::Clock,
+            vec![PTE::default(); 4],
+            vec![0, 1, 2],
+            source,
+        );
+        let first: Vec<PageEvent> = events.by_ref().take(10).collect();
+
+        assert_eq!(pulled.get(), 10);
+        assert_eq!(first.len(), 10);
+        assert_eq!(events.translator().faults, 10);
+        assert!(events.next().unwrap().is_fault());
+    }
+
+    #[test]
+    pub fn test_preloaded_pages_and_bad_references() {
+        let mut page_table = vec![PTE::default(); 4];
+        page_table[1] = PTE {
+            is_valid: true,
+            frame_number: 5,
+            arrival_timestamp: 1,
+            last_access_timestamp: 1,
+            reference_count: 1,
+            ..PTE::default()
+        };
+
+        let events: Vec<PageEvent> =
+            fault_events_fifo(page_table, vec![6], vec![1, -1, 9, 2, 1]).collect();
+
+        let kinds: Vec<EventKind> = events.iter().map(|e| e.kind).collect();
+        assert_eq!(
+            kinds,
+            vec![
+                EventKind::Hit,
+                EventKind::OutOfRange,
+                EventKind::OutOfRange,
+                EventKind::ColdMiss,
+                EventKind::Hit,
+            ]
+        );
+        assert_eq!(events[0].frame, 5);
+        let summary = summarize_events(events);
+        assert_eq!(summary.out_of_range, 2);
+        assert_eq!(summary.faults(), 1);
+    }
+}
@@ -0,0 +1,141 @@
+#[cfg(test)]
+
+mod test_huge {
+    use crate::r#virtual::huge::{
+        compare_page_sizes, tlb_reach, ContiguousFrameAllocator, HugeAccess, HugePageConfig,
+        HugePageSimulator, PageSize, PageSizeMode, BASE_PAGE_BYTES,
+    };
+
+    fn small_config(mode: PageSizeMode) -> HugePageConfig {
+        HugePageConfig {
+            memory_frames: 4096,
+            mode,
+            ..HugePageConfig::default()
+        }
+    }
+
+    #[test]
+    pub fn test_page_sizes_and_tlb_reach() {
+        assert_eq!(PageSize::Huge2M.base_pages(), 512);
+        assert_eq!(PageSize::Huge1G.base_pages(), 262144);
+        assert_eq!(tlb_reach(64, PageSize::Base4K), 256 * 1024);
+        assert_eq!(
+            HugePageConfig::default().tlb_reach(),
+            64 * 4096 + 32 * (2 << 20) + 4 * (1 << 30)
+        );
+    }
+
+    #[test]
+    pub fn test_contiguous_aligned_allocation() {
+        let mut allocator = ContiguousFrameAllocator::new(1024);
+
+        assert_eq!(allocator.allocate(PageSize::Base4K), Some(0));
+        assert_eq!(allocator.allocate(PageSize::Huge2M), Some(512));
+        assert_eq!(allocator.allocate(PageSize::Huge2M), None);
+        assert_eq!(allocator.free_frames(), 511);
+        assert_eq!(allocator.fragmentation(PageSize::Huge2M), 1.0);
+
+        allocator.free(0, PageSize::Base4K);
+        assert_eq!(allocator.available(PageSize::Huge2M), 1);
+        assert_eq!(allocator.fragmentation(PageSize::Huge2M), 0.0);
+    }
+
+    #[test]
+    pub fn test_huge_pages_cut_tlb_misses() {
+        let pages: Vec<u64> = (0..1024).map(|page| page * BASE_PAGE_BYTES).collect();
+        let addresses: Vec<u64> = pages.iter().chain(pages.iter()).copied().collect();
+
+        let results = compare_page_sizes(
+            small_config(PageSizeMode::BaseOnly),
+            &[PageSizeMode::BaseOnly, PageSizeMode::Huge2M],
+            &addresses,
+        );
+
+        assert_eq!(results[0].stats.page_faults, 1024);
+        assert_eq!(results[0].stats.tlb_misses, 2048);
+        assert_eq!(results[1].stats.page_faults, 2);
+        assert_eq!(results[1].stats.tlb_misses, 2);
+        assert_eq!(results[0].internal_fragmentation_bytes, 0);
+        assert_eq!(results[1].internal_fragmentation_bytes, 0);
+    }
+
+    #[test]
+    pub fn test_internal_fragmentation() {
+        let results = compare_page_sizes(
+            small_config(PageSizeMode::BaseOnly),
+            &[PageSizeMode::BaseOnly, PageSizeMode::Huge2M],
+            &[0x1234],
+        );
+
+        assert_eq!(results[0].mapped_bytes, 4096);
+        assert_eq!(results[0].internal_fragmentation_bytes, 0);
+        assert_eq!(results[1].mapped_bytes, 2 << 20);
+        assert_eq!(results[1].internal_fragmentation_bytes, (2 << 20) - 4096);
+    }
+
+    #[test]
+    pub fn test_fallback_without_contiguous_frames() {
+        let mut simulator = HugePageSimulator::new(HugePageConfig {
+            memory_frames: 600,
+            ..small_config(PageSizeMode::Huge1G)
+        });
+
+        assert_eq!(
+            simulator.access(0x10),
+            HugeAccess::Fault {
+                page_size: PageSize::Huge2M,
+                physical_address: 0x10
+            }
+        );
+        assert_eq!(
+            simulator.access((2 << 20) + 0x20),
+            HugeAccess::Fault {
+                page_size: PageSize::Base4K,
+                physical_address: 512 * 4096 + 0x20
+            }
+        );
+        assert_eq!(simulator.stats.huge_fallbacks, 2);
+
+        let mut full = HugePageSimulator::new(HugePageConfig {
+            memory_frames: 1,
+            ..small_config(PageSizeMode::BaseOnly)
+        });
+        full.access(0);
+        assert_eq!(full.access(4096), HugeAccess::OutOfMemory);
+    }
+
+    #[test]
+    pub fn test_transparent_promotion_and_demotion() {
+        let mut simulator = HugePageSimulator::new(small_config(PageSizeMode::Transparent));
+
+        for page in 0..256 {
+            simulator.access(page * BASE_PAGE_BYTES);
+        }
+        assert_eq!(simulator.stats.promotions, 1);
+        assert!(matches!(
+            simulator.access(300 * BASE_PAGE_BYTES),
+            HugeAccess::Hit {
+                page_size: PageSize::Huge2M,
+                ..
+            }
+        ));
+        simulator.end_epoch();
+        assert_eq!(simulator.stats.demotions, 0);
+
+        for page in 0..10 {
+            simulator.access(page * BASE_PAGE_BYTES);
+        }
+        simulator.end_epoch();
+
+        assert_eq!(simulator.stats.demotions, 1);
+        assert_eq!(simulator.mapped_bytes(), 10 * BASE_PAGE_BYTES);
+        assert_eq!(simulator.allocator.free_frames(), 4096 - 10);
+        assert!(matches!(
+            simulator.access(5 * BASE_PAGE_BYTES),
+            HugeAccess::Hit {
+                page_size: PageSize::Base4K,
+                ..
+            }
+        ));
+    }
+}
@@ -0,0 +1,144 @@
+#[cfg(test)]
+
+mod test_mmap {
+    use crate::r#virtual::mmap::{Mapping, MappingKind, MmapMemory, MmapOutcome, PageKey};
+    use crate::r#virtual::r#virtual::ReplacementPolicy;
+
+    fn file_mapping(start_page: usize, page_count: usize, file_offset: usize) -> Mapping {
+        Mapping {
+            start_page,
+            page_count,
+            kind: MappingKind::File {
+                file_id: 7,
+                file_offset,
+            },
+        }
+    }
+
+    fn anonymous_mapping(start_page: usize, page_count: usize) -> Mapping {
+        Mapping {
+            start_page,
+            page_count,
+            kind: MappingKind::Anonymous,
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "mmap memory supports FIFO, LRU, LFU and MRU, not LIRS")]
+    pub fn test_rejects_stateful_policy() {
+        MmapMemory::new(2, ReplacementPolicy::Lirs);
+    }
+
+    #[test]
+    pub fn test_page_cache_shared_between_processes() {
+        let mut memory = MmapMemory::new(4, ReplacementPolicy::Lru);
+        memory.mmap(1, file_mapping(0, 4, 0));
+        memory.mmap(2, file_mapping(10, 2, 2));
+
+        for page_number in 0..4 {
+            memory.access(1, page_number, false);
+        }
+        let frame = match memory.access(1, 2, false) {
+            MmapOutcome::Hit { frame } => frame,
+            outcome => panic!("unexpected {:?}", outcome),
+        };
+
+        assert_eq!(memory.access(2, 10, false), MmapOutcome::Hit { frame });
+        assert!(matches!(
+            memory.access(2, 11, false),
+            MmapOutcome::Hit { .. }
+        ));
+        assert_eq!(memory.stats.cache_misses, 4);
+        assert_eq!(memory.stats.cache_hits, 3);
+        assert_eq!(memory.stats.page_cache_hit_rate(), 3.0 / 7.0);
+        assert_eq!(memory.cached_pages(7), 4);
+    }
+
+    #[test]
+    pub fn test_clean_drop_and_dirty_flush() {
+        let mut memory = MmapMemory::new(2, ReplacementPolicy::Lru);
+        memory.mmap(1, file_mapping(0, 4, 0));
+
+        memory.access(1, 0, true);
+        memory.access(1, 1, false);
+        assert_eq!(
+            memory.access(1, 2, false),
+            MmapOutcome::Fault {
+                frame: 0,
+                evicted: Some(PageKey::File {
+                    file_id: 7,
+                    file_page: 0
+                })
+            }
+        );
+        memory.access(1, 3, false);
+
+        assert_eq!(memory.stats.dirty_flushes, 1);
+        assert_eq!(memory.stats.clean_drops, 1);
+        assert_eq!(memory.stats.swap_outs, 0);
+    }
+
+    #[test]
+    pub fn test_anonymous_pages_need_swap() {
+        let mut memory = MmapMemory::new(2, ReplacementPolicy::Lru);
+        memory.mmap(1, anonymous_mapping(0, 3));
+
+        memory.access(1, 0, true);
+        memory.access(1, 1, true);
+        assert_eq!(
+            memory.access(1, 2, false),
+            MmapOutcome::Fault {
+                frame: 0,
+                evicted: Some(PageKey::Anonymous {
+                    pid: 1,
+                    page_number: 0
+                })
+            }
+        );
+        memory.access(1, 0, false);
+
+        assert_eq!(memory.stats.zero_fill_faults, 3);
+        assert_eq!(memory.stats.swap_outs, 2);
+        assert_eq!(memory.stats.swap_ins, 1);
+        assert!(memory.swapped.contains(&PageKey::Anonymous {
+            pid: 1,
+            page_number: 1
+        }));
+        assert_eq!(memory.stats.page_cache_hit_rate(), 0.0);
+    }
+
+    #[test]
+    pub fn test_msync_cleans_dirty_pages() {
+        let mut memory = MmapMemory::new(2, ReplacementPolicy::Fifo);
+        memory.mmap(1, file_mapping(0, 4, 0));
+        memory.access(1, 0, true);
+        memory.access(1, 1, true);
+
+        assert_eq!(memory.msync(7), 2);
+        assert_eq!(memory.msync(7), 0);
+        memory.access(1, 2, false);
+        memory.access(1, 3, false);
+
+        assert_eq!(memory.stats.dirty_flushes, 2);
+        assert_eq!(memory.stats.clean_drops, 2);
+    }
+
+    #[test]
+    pub fn test_mapping_bookkeeping() {
+        let mut memory = MmapMemory::new(4, ReplacementPolicy::Lru);
+        assert!(memory.mmap(1, anonymous_mapping(0, 2)));
+        assert!(!memory.mmap(1, file_mapping(1, 2, 0)));
+        assert!(memory.mmap(1, file_mapping(2, 2, 0)));
+
+        assert_eq!(memory.access(1, 4, false), MmapOutcome::Segfault);
+        assert_eq!(memory.access(2, 0, false), MmapOutcome::Segfault);
+
+        memory.access(1, 0, true);
+        memory.access(1, 2, false);
+        assert!(memory.munmap(1, 0));
+        assert!(memory.munmap(1, 2));
+        assert!(!memory.munmap(1, 2));
+        assert_eq!(memory.frame_pool.len(), 3);
+        assert_eq!(memory.cached_pages(7), 1);
+    }
+}
@@ -0,0 +1,172 @@
+#[cfg(test)]
+
+mod test_multilevel {
+    use crate::r#virtual::multilevel::{
+        MultiLevelConfig, MultiLevelPageTable, Translation, TranslationFault,
+    };
+
+    #[test]
+    pub fn test_split_address() {
+        let config = MultiLevelConfig::two_level_32bit();
+
+        let (indices, offset) = config.split_address(0x1234_5abc);
+
+        assert_eq!(indices, vec![0x48, 0x345]);
+        assert_eq!(offset, 0xabc);
+        assert_eq!(config.address_bits(), 32);
+        assert_eq!(config.page_size(), 4096);
+    }
+
+    #[test]
+    pub fn test_split_address_full_64bit() {
+        let config = MultiLevelConfig::new(vec![20, 20, 12], 12, 8);
+
+        let (indices, offset) = config.split_address(u64::MAX);
+
+        assert_eq!(indices, vec![0xf_ffff, 0xf_ffff, 0xfff]);
+        assert_eq!(offset, 0xfff);
+        assert_eq!(config.address_bits(), 64);
+    }
+
+    #[test]
+    pub fn test_translate_full_64bit() {
+        let mut page_table =
+            MultiLevelPageTable::new(MultiLevelConfig::new(vec![20, 20, 12], 12, 8));
+
+        assert!(page_table.map(0xffff_ffff_ffff_f000, 3));
+
+        assert_eq!(
+            page_table
+                .translate(0xffff_ffff_ffff_f123)
+                .map(|translation| translation.physical_address),
+            Ok(0x3123)
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "64-bit address")]
+    pub fn test_config_rejects_more_than_64_bits() {
+        MultiLevelConfig::new(vec![20, 20, 20], 12, 8);
+    }
+
+    #[test]
+    #[should_panic(expected = "at most 24 bits")]
+    pub fn test_config_rejects_oversized_level() {
+        MultiLevelConfig::new(vec![32], 12, 8);
+    }
+
+    #[test]
+    #[should_panic(expected = "at least one level")]
+    pub fn test_page_table_rejects_config_without_levels() {
+        MultiLevelPageTable::new(MultiLevelConfig {
+            level_bits: Vec::new(),
+            offset_bits: 12,
+            entry_size: 8,
+        });
+    }
+
+    #[test]
+    pub fn test_translate_two_level() {
+        let mut page_table = MultiLevelPageTable::new(MultiLevelConfig::two_level_32bit());
+
+        assert!(page_table.map(0x1234_5000, 7));
+        let translation = page_table.translate(0x1234_5abc);
+
+        assert_eq!(
+            translation,
+            Ok(Translation {
+                physical_address: 0x7abc,
+                frame_number: 7,
+                memory_accesses: 2,
+            })
+        );
+        assert_eq!(page_table.table_count(), 2);
+        assert_eq!(page_table.memory_overhead_bytes(), 8192);
+    }
+
+    #[test]
+    pub fn test_intermediate_tables_allocated_on_demand() {
+        let mut page_table = MultiLevelPageTable::new(MultiLevelConfig::two_level_32bit());
+
+        page_table.map(0x1234_5000, 7);
+        page_table.map(0x1234_6000, 8);
+        assert_eq!(page_table.table_count(), 2);
+
+        page_table.map(0xffff_f000, 9);
+        assert_eq!(page_table.table_count(), 3);
+        assert_eq!(page_table.memory_overhead_bytes(), 3 * 4096);
+    }
+
+    #[test]
+    pub fn test_translate_not_mapped() {
+        let mut page_table = MultiLevelPageTable::new(MultiLevelConfig::two_level_32bit());
+        page_table.map(0x1234_5000, 7);
+
+        assert_eq!(
+            page_table.translate(0x8000_0000),
+            Err(TranslationFault::NotMapped {
+                level: 0,
+                memory_accesses: 1
+            })
+        );
+        assert_eq!(
+            page_table.translate(0x1234_6000),
+            Err(TranslationFault::NotMapped {
+                level: 1,
+                memory_accesses: 2
+            })
+        );
+        assert_eq!(
+            page_table.translate(0x1_0000_0000),
+            Err(TranslationFault::AddressOutOfRange)
+        );
+    }
+
+    #[test]
+    pub fn test_translate_sparse_48bit_address_space() {
+        let mut page_table = MultiLevelPageTable::new(MultiLevelConfig::x86_64_four_level());
+
+        page_table.map(0x0000_0000_0040_0000, 1);
+        page_table.map(0x0000_7fff_ffff_f000, 2);
+
+        assert_eq!(page_table.table_count(), 7);
+        assert_eq!(page_table.memory_overhead_bytes(), 7 * 4096);
+        assert_eq!(page_table.flat_table_bytes(), 1u128 << 39);
+        assert_eq!(
+            page_table.translate(0x0000_7fff_ffff_f123).unwrap(),
+            Translation {
+                physical_address: 0x2123,
+                frame_number: 2,
+                memory_accesses: 4,
+            }
+        );
+    }
+
+    #[test]
+    pub fn test_walk_stats() {
+        let mut page_table = MultiLevelPageTable::new(MultiLevelConfig::x86_64_four_level());
+        page_table.map(0x40_0000, 1);
+
+        page_table.translate(0x40_0010).unwrap();
+        page_table.translate(0x40_0020).unwrap();
+        page_table.translate(0x7fff_0000_0000).unwrap_err();
+
+        assert_eq!(page_table.stats.translations, 2);
+        assert_eq!(page_table.stats.faults, 1);
+        assert_eq!(page_table.stats.memory_accesses, 9);
+        assert_eq!(page_table.stats.average_memory_accesses(), 3.0);
+    }
+
+    #[test]
+    pub fn test_unmap() {
+        let mut page_table = MultiLevelPageTable::new(MultiLevelConfig::two_level_32bit());
+        page_table.map(0x1234_5000, 7);
+
+        let pte = page_table.unmap(0x1234_5000).unwrap();
+
+        assert_eq!(pte.frame_number, 7);
+        assert_eq!(page_table.lookup(0x1234_5000), None);
+        assert_eq!(page_table.unmap(0x1234_5000), None);
+        assert_eq!(page_table.table_count(), 2);
+    }
+}
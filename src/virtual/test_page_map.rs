@@ -0,0 +1,185 @@
+#[cfg(test)]
+
+mod test_page_map {
+    use crate::r#virtual::page_map::{
+        count_page_faults_page_map, FlatPageTable, HashedPageTable, InvertedPageTable, MappedPage,
+        PageMap,
+    };
+    use crate::r#virtual::r#virtual::{ReplacementPolicy, PTE};
+    use std::mem::size_of;
+
+    fn mapped_pte(frame_number: i32) -> PTE {
+        PTE {
+            is_valid: true,
+            frame_number,
+            arrival_timestamp: 1,
+            last_access_timestamp: 1,
+            reference_count: 1,
+            ..PTE::default()
+        }
+    }
+
+    #[test]
+    pub fn test_page_maps_match_vec_page_table() {
+        let reference_strings = [
+            vec![0, 3, 2, 6, 3, 4, 5, 2, 4, 5, 6],
+            vec![0, 3, 2, 6, 3, 4, 5, 2, 6, 4, 5],
+            vec![1, 2, 3, 4, 1, 2, 5, 1, 2, 3, 4, 5],
+        ];
+        let policies = [
+            ReplacementPolicy::Fifo,
+            ReplacementPolicy::Lru,
+            ReplacementPolicy::Lfu,
+            ReplacementPolicy::Mru,
+        ];
+
+        for reference_string in reference_strings.iter() {
+            for policy in policies.iter() {
+                let expected = policy.count_page_faults_for_frames(reference_string, 3);
+                let mut page_maps: Vec<Box<dyn PageMap>> = vec![
+                    Box::new(FlatPageTable::new(8)),
+                    Box::new(InvertedPageTable::new(3)),
+                    Box::new(HashedPageTable::new(4)),
+                ];
+
+                for page_map in page_maps.iter_mut() {
+                    let mut frame_pool: Vec<i32> = vec![2, 1, 0];
+                    let faults = count_page_faults_page_map(
+                        page_map.as_mut(),
+                        1,
+                        reference_string.clone(),
+                        &mut frame_pool,
+                        *policy,
+                    );
+
+                    assert_eq!(faults, Some(expected));
+                    assert_eq!(page_map.resident().len(), 3);
+                }
+            }
+        }
+    }
+
+    #[test]
+    pub fn test_page_map_rejects_stateful_policies() {
+        let policies = [
+            ReplacementPolicy::Random(42),
+            ReplacementPolicy::Clock,
+            ReplacementPolicy::Arc,
+        ];
+
+        for policy in policies.iter() {
+            let mut page_map = FlatPageTable::new(8);
+            let mut frame_pool: Vec<i32> = vec![2, 1, 0];
+            let faults = count_page_faults_page_map(
+                &mut page_map,
+                1,
+                vec![1, 2, 3, 4, 1, 2, 5],
+                &mut frame_pool,
+                *policy,
+            );
+
+            assert_eq!(faults, None);
+            assert_eq!(page_map.resident(), Vec::new());
+            assert_eq!(frame_pool, vec![2, 1, 0]);
+        }
+    }
+
+    #[test]
+    pub fn test_inverted_page_table_probe_length() {
+        let mut page_table = InvertedPageTable::new(4);
+
+        page_table.insert(1, 10, mapped_pte(0));
+        page_table.insert(1, 11, mapped_pte(2));
+
+        assert_eq!(page_table.lookup(1, 10).unwrap().frame_number, 0);
+        assert_eq!(page_table.lookup(1, 11).unwrap().frame_number, 2);
+        assert_eq!(page_table.lookup(1, 12), None);
+
+        assert_eq!(page_table.stats().lookups, 3);
+        assert_eq!(page_table.stats().probes, 1 + 3 + 4);
+        assert!((page_table.stats().average_probe_length() - 8.0 / 3.0).abs() < 1e-9);
+    }
+
+    #[test]
+    pub fn test_inverted_page_table_keys_by_pid() {
+        let mut page_table = InvertedPageTable::new(4);
+
+        page_table.insert(1, 10, mapped_pte(0));
+        page_table.insert(2, 10, mapped_pte(1));
+
+        assert_eq!(page_table.lookup(1, 10).unwrap().frame_number, 0);
+        assert_eq!(page_table.lookup(2, 10).unwrap().frame_number, 1);
+        assert_eq!(page_table.evict(2, 10).unwrap().frame_number, 1);
+        assert_eq!(page_table.lookup(2, 10), None);
+        assert_eq!(
+            page_table.frames[0],
+            Some(MappedPage {
+                pid: 1,
+                page_number: 10,
+                pte: mapped_pte(0)
+            })
+        );
+        assert!(!page_table.insert(1, 12, mapped_pte(4)));
+    }
+
+    #[test]
+    pub fn test_hashed_page_table_chaining() {
+        let mut page_table = HashedPageTable::new(1);
+
+        page_table.insert(1, 10, mapped_pte(0));
+        page_table.insert(1, 11, mapped_pte(1));
+        page_table.insert(2, 10, mapped_pte(2));
+
+        assert_eq!(page_table.longest_chain(), 3);
+        assert_eq!(page_table.lookup(2, 10).unwrap().frame_number, 2);
+        assert_eq!(page_table.lookup(1, 12), None);
+        assert_eq!(page_table.stats().probes, 6);
+
+        assert_eq!(page_table.evict(1, 11).unwrap().frame_number, 1);
+        assert_eq!(page_table.longest_chain(), 2);
+        assert_eq!(page_table.evict(1, 11), None);
+    }
+
+    #[test]
+    pub fn test_hashed_page_table_spreads_keys() {
+        let mut page_table = HashedPageTable::new(64);
+
+        for page_number in 0..64 {
+            page_table.insert(1, page_number, mapped_pte(page_number as i32));
+        }
+        for page_number in 0..64 {
+            page_table.lookup(1, page_number);
+        }
+
+        assert!(page_table.longest_chain() < 8);
+        assert!(page_table.stats().average_probe_length() < 3.0);
+    }
+
+    #[test]
+    pub fn test_memory_footprint() {
+        let mut flat = FlatPageTable::new(1024);
+        let mut inverted = InvertedPageTable::new(16);
+        let bucket_count = 8;
+        let mut hashed = HashedPageTable::new(bucket_count);
+
+        for pid in 1..3 {
+            for page_number in 0..4 {
+                let pte = mapped_pte((pid - 1) * 4 + page_number);
+                flat.insert(pid, page_number as usize, pte);
+                inverted.insert(pid, page_number as usize, pte);
+                hashed.insert(pid, page_number as usize, pte);
+            }
+        }
+
+        assert_eq!(flat.memory_footprint_bytes(), 2 * 1024 * size_of::<PTE>());
+        assert_eq!(
+            inverted.memory_footprint_bytes(),
+            16 * size_of::<Option<MappedPage>>()
+        );
+        assert_eq!(
+            hashed.memory_footprint_bytes(),
+            bucket_count * size_of::<usize>() + 8 * (size_of::<MappedPage>() + size_of::<usize>())
+        );
+        assert!(inverted.memory_footprint_bytes() < flat.memory_footprint_bytes());
+    }
+}
@@ -0,0 +1,206 @@
+#[cfg(test)]
+
+mod test_pager {
+    use crate::r#virtual::pager::{
+        FrameAllocation, MultiProcessPager, PagerOutcome, ReplacementScope,
+    };
+    use crate::r#virtual::r#virtual::ReplacementPolicy;
+
+    #[test]
+    pub fn test_equal_allocation() {
+        let mut pager = MultiProcessPager::new(
+            7,
+            ReplacementPolicy::Lru,
+            ReplacementScope::Local,
+            FrameAllocation::Equal,
+        );
+
+        pager.add_process(1, 10);
+        pager.add_process(2, 20);
+        pager.add_process(3, 40);
+
+        let limits: Vec<usize> = pager.processes.iter().map(|p| p.frame_limit).collect();
+        assert_eq!(limits, vec![3, 2, 2]);
+        assert!(!pager.add_process(2, 5));
+    }
+
+    #[test]
+    pub fn test_proportional_allocation() {
+        let mut pager = MultiProcessPager::new(
+            7,
+            ReplacementPolicy::Lru,
+            ReplacementScope::Local,
+            FrameAllocation::Proportional,
+        );
+
+        pager.add_process(1, 10);
+        pager.add_process(2, 20);
+
+        assert_eq!(pager.process(1).unwrap().frame_limit, 3);
+        assert_eq!(pager.process(2).unwrap().frame_limit, 4);
+    }
+
+    #[test]
+    #[should_panic(expected = "the pager supports FIFO, LRU, LFU and MRU, not ARC")]
+    pub fn test_pager_rejects_stateful_policy() {
+        MultiProcessPager::new(
+            2,
+            ReplacementPolicy::Arc,
+            ReplacementScope::Local,
+            FrameAllocation::Equal,
+        );
+    }
+
+    #[test]
+    pub fn test_manual_allocation_keeps_limits() {
+        let mut pager = MultiProcessPager::new(
+            7,
+            ReplacementPolicy::Lru,
+            ReplacementScope::Local,
+            FrameAllocation::Manual,
+        );
+
+        pager.add_process(1, 10);
+        pager.set_frame_limit(1, 5);
+        pager.add_process(2, 20);
+        pager.rebalance();
+
+        assert_eq!(pager.process(1).unwrap().frame_limit, 5);
+        assert_eq!(pager.process(2).unwrap().frame_limit, 0);
+    }
+
+    #[test]
+    pub fn test_local_replacement_evicts_own_pages() {
+        let mut pager = MultiProcessPager::new(
+            4,
+            ReplacementPolicy::Lru,
+            ReplacementScope::Local,
+            FrameAllocation::Equal,
+        );
+        pager.add_process(1, 8);
+        pager.add_process(2, 8);
+
+        let outcomes = pager.run(&[(1, 0), (1, 1), (1, 2), (2, 0)]);
+
+        assert_eq!(
+            outcomes[2],
+            PagerOutcome::Fault {
+                frame: 0,
+                evicted: Some((1, 0))
+            }
+        );
+        assert_eq!(
+            outcomes[3],
+            PagerOutcome::Fault {
+                frame: 2,
+                evicted: None
+            }
+        );
+        assert_eq!(pager.process(1).unwrap().resident_count(), 2);
+        assert_eq!(pager.process(1).unwrap().stats.pages_evicted, 1);
+        assert_eq!(pager.frame_pool, vec![3]);
+    }
+
+    #[test]
+    pub fn test_global_replacement_takes_frames_from_other_processes() {
+        let mut pager = MultiProcessPager::new(
+            4,
+            ReplacementPolicy::Lru,
+            ReplacementScope::Global,
+            FrameAllocation::Equal,
+        );
+        pager.add_process(1, 8);
+        pager.add_process(2, 8);
+
+        let outcomes = pager.run(&[(1, 0), (1, 1), (2, 0), (2, 1), (1, 2), (2, 2), (1, 3)]);
+
+        assert_eq!(
+            outcomes[4],
+            PagerOutcome::Fault {
+                frame: 0,
+                evicted: Some((1, 0))
+            }
+        );
+        assert_eq!(
+            outcomes[5],
+            PagerOutcome::Fault {
+                frame: 1,
+                evicted: Some((1, 1))
+            }
+        );
+        assert_eq!(
+            outcomes[6],
+            PagerOutcome::Fault {
+                frame: 2,
+                evicted: Some((2, 0))
+            }
+        );
+        assert_eq!(pager.process(1).unwrap().resident_count(), 2);
+        assert_eq!(pager.process(2).unwrap().resident_count(), 2);
+        assert_eq!(pager.process(1).unwrap().stats.faults, 4);
+        assert_eq!(pager.process(2).unwrap().stats.pages_evicted, 1);
+        assert_eq!(pager.total_faults(), 7);
+    }
+
+    #[test]
+    pub fn test_per_process_stats() {
+        let mut pager = MultiProcessPager::new(
+            4,
+            ReplacementPolicy::Fifo,
+            ReplacementScope::Local,
+            FrameAllocation::Equal,
+        );
+        pager.add_process(1, 8);
+        pager.add_process(2, 8);
+
+        pager.run(&[(1, 0), (1, 0), (1, 1), (1, 0), (2, 5), (2, 5)]);
+
+        let stats = pager.process(1).unwrap().stats;
+        assert_eq!(stats.accesses, 4);
+        assert_eq!(stats.faults, 2);
+        assert_eq!(stats.fault_rate(), 0.5);
+        assert_eq!(pager.process(2).unwrap().stats.fault_rate(), 0.5);
+    }
+
+    #[test]
+    pub fn test_remove_process_returns_frames() {
+        let mut pager = MultiProcessPager::new(
+            4,
+            ReplacementPolicy::Lru,
+            ReplacementScope::Local,
+            FrameAllocation::Equal,
+        );
+        pager.add_process(1, 8);
+        pager.add_process(2, 8);
+        pager.run(&[(1, 0), (1, 1), (2, 0)]);
+
+        let removed = pager.remove_process(1).unwrap();
+
+        assert_eq!(removed.resident_count(), 2);
+        assert_eq!(pager.frame_pool.len(), 3);
+        assert_eq!(pager.process(2).unwrap().frame_limit, 4);
+        assert_eq!(pager.access(1, 0), PagerOutcome::UnknownProcess);
+        assert_eq!(pager.access(2, 8), PagerOutcome::OutOfRange);
+    }
+
+    #[test]
+    pub fn test_set_frame_limit_reclaims_frames() {
+        let mut pager = MultiProcessPager::new(
+            4,
+            ReplacementPolicy::Lru,
+            ReplacementScope::Local,
+            FrameAllocation::Equal,
+        );
+        pager.add_process(1, 8);
+        pager.set_frame_limit(1, 4);
+        pager.run(&[(1, 0), (1, 1), (1, 2), (1, 3), (1, 0)]);
+
+        pager.set_frame_limit(1, 2);
+
+        let process = pager.process(1).unwrap();
+        assert_eq!(process.resident_count(), 2);
+        assert!(process.page_table[0].is_valid);
+        assert!(process.page_table[3].is_valid);
+        assert_eq!(pager.frame_pool.len(), 2);
+    }
+}
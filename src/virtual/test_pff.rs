@@ -0,0 +1,185 @@
+#[cfg(test)]
+
+mod test_pff {
+    use crate::r#virtual::pager::{FrameAllocation, MultiProcessPager, ReplacementScope};
+    use crate::r#virtual::pff::{PffAction, PffConfig, PffController};
+    use crate::r#virtual::r#virtual::ReplacementPolicy;
+
+    fn test_config() -> PffConfig {
+        PffConfig {
+            window: 4,
+            upper_threshold: 0.5,
+            lower_threshold: 0.3,
+            step: 1,
+            min_frames: 2,
+        }
+    }
+
+    #[test]
+    pub fn test_processes_start_at_min_frames() {
+        let mut controller = PffController::new(5, ReplacementPolicy::Lru, test_config());
+        controller.add_process(1, 16);
+        controller.add_process(2, 16);
+
+        assert_eq!(controller.pager.process(1).unwrap().frame_limit, 2);
+        assert_eq!(controller.pager.process(2).unwrap().frame_limit, 2);
+        assert_eq!(controller.unallocated_frames(), 1);
+
+        controller.add_process(3, 16);
+        assert_eq!(controller.pager.process(3).unwrap().frame_limit, 1);
+        assert_eq!(controller.pager.process(1).unwrap().frame_limit, 2);
+        assert_eq!(controller.unallocated_frames(), 0);
+        assert!(!controller.add_process(3, 16));
+    }
+
+    #[test]
+    pub fn test_high_fault_process_is_granted() {
+        let mut controller = PffController::new(8, ReplacementPolicy::Lru, test_config());
+        controller.add_process(1, 16);
+        controller.add_process(2, 16);
+
+        controller.run(&[(1, 0), (1, 1), (1, 2), (1, 3)]);
+        assert_eq!(controller.trace.len(), 1);
+        assert_eq!(controller.trace[0].action, PffAction::Grant);
+        assert_eq!(controller.trace[0].fault_rate, 1.0);
+        assert_eq!(controller.trace[0].old_limit, 2);
+        assert_eq!(controller.trace[0].new_limit, 3);
+        assert_eq!(controller.unallocated_frames(), 3);
+
+        controller.run(&[(1, 4), (1, 5), (1, 6), (1, 7)]);
+        assert_eq!(controller.trace[1].action, PffAction::Grant);
+        assert_eq!(controller.pager.process(1).unwrap().frame_limit, 4);
+        assert_eq!(controller.pager.process(1).unwrap().resident_count(), 3);
+        assert_eq!(controller.pager.process(2).unwrap().frame_limit, 2);
+        assert_eq!(controller.unallocated_frames(), 2);
+    }
+
+    #[test]
+    pub fn test_grant_then_reclaim() {
+        let mut controller = PffController::new(6, ReplacementPolicy::Lru, test_config());
+        controller.add_process(1, 16);
+
+        controller.run(&[(1, 0), (1, 1), (1, 2), (1, 3)]);
+        controller.run(&[(1, 3), (1, 3), (1, 3), (1, 3)]);
+
+        assert_eq!(controller.trace.len(), 2);
+        assert_eq!(controller.trace[0].action, PffAction::Grant);
+        assert_eq!(controller.trace[1].action, PffAction::Reclaim);
+        assert_eq!(controller.trace[1].old_limit, 3);
+        assert_eq!(controller.trace[1].new_limit, 2);
+        assert_eq!(controller.pager.process(1).unwrap().resident_count(), 2);
+        assert_eq!(controller.unallocated_frames(), 4);
+    }
+
+    #[test]
+    #[should_panic(expected = "not CLOCK")]
+    pub fn test_controller_rejects_stateful_policy() {
+        PffController::new(4, ReplacementPolicy::Clock, test_config());
+    }
+
+    #[test]
+    #[should_panic(expected = "PFF supports FIFO, LRU, LFU and MRU, not Random")]
+    pub fn test_from_pager_rejects_stateful_policy() {
+        let mut pager = MultiProcessPager::new(
+            4,
+            ReplacementPolicy::Lru,
+            ReplacementScope::Local,
+            FrameAllocation::Manual,
+        );
+        pager.policy = ReplacementPolicy::Random(3);
+
+        PffController::from_pager(pager, test_config());
+    }
+
+    #[test]
+    pub fn test_grant_denied_without_free_frames() {
+        let mut controller = PffController::new(2, ReplacementPolicy::Fifo, test_config());
+        controller.add_process(1, 8);
+
+        controller.run(&[(1, 0), (1, 1), (1, 2), (1, 0)]);
+
+        assert_eq!(controller.trace.len(), 1);
+        assert_eq!(controller.trace[0].action, PffAction::Denied);
+        assert_eq!(controller.trace[0].new_limit, 2);
+        assert_eq!(controller.pager.process(1).unwrap().frame_limit, 2);
+    }
+
+    #[test]
+    pub fn test_reclaim_stops_at_min_frames() {
+        let mut controller = PffController::new(6, ReplacementPolicy::Lru, test_config());
+        controller.add_process(1, 16);
+        let growing: Vec<(i32, usize)> = (0..16).map(|page_number| (1, page_number)).collect();
+        controller.run(&growing);
+        assert_eq!(controller.pager.process(1).unwrap().frame_limit, 6);
+
+        for _ in 0..5 {
+            controller.run(&[(1, 15), (1, 15), (1, 15), (1, 15)]);
+        }
+
+        let process = controller.pager.process(1).unwrap();
+        assert_eq!(process.frame_limit, 2);
+        assert_eq!(process.resident_count(), 2);
+        assert_eq!(controller.pager.frame_pool.len(), 4);
+        assert_eq!(
+            controller.limit_history(1),
+            vec![
+                (0, 2),
+                (4, 3),
+                (8, 4),
+                (12, 5),
+                (16, 6),
+                (20, 5),
+                (24, 4),
+                (28, 3),
+                (32, 2)
+            ]
+        );
+    }
+
+    #[test]
+    pub fn test_pff_with_each_policy() {
+        let trace: Vec<(i32, usize)> = (0..40)
+            .map(|i| if i % 2 == 0 { (1, (i / 2) % 6) } else { (2, 0) })
+            .collect();
+
+        for &policy in &[
+            ReplacementPolicy::Fifo,
+            ReplacementPolicy::Lru,
+            ReplacementPolicy::Lfu,
+        ] {
+            let mut controller = PffController::new(8, policy, test_config());
+            controller.add_process(1, 8);
+            controller.add_process(2, 8);
+
+            controller.run(&trace);
+
+            let grants = controller
+                .trace
+                .iter()
+                .filter(|d| d.pid == 1 && d.action == PffAction::Grant)
+                .count();
+            let reclaims = controller
+                .trace
+                .iter()
+                .filter(|d| d.pid == 1 && d.action == PffAction::Reclaim)
+                .count();
+            assert!(grants > 0, "{}", policy.name());
+            assert!(reclaims > 0, "{}", policy.name());
+            assert!(
+                controller.trace.iter().all(|d| d.pid == 1),
+                "{}",
+                policy.name()
+            );
+            assert_eq!(controller.pager.process(2).unwrap().frame_limit, 2);
+            let peak = controller
+                .limit_history(1)
+                .iter()
+                .map(|&(_, limit)| limit)
+                .max();
+            assert_eq!(peak, Some(6));
+            for process in controller.pager.processes.iter() {
+                assert!(process.resident_count() <= process.frame_limit);
+            }
+        }
+    }
+}
@@ -0,0 +1,109 @@
+#[cfg(test)]
+
+mod test_prefetch {
+    use crate::r#virtual::prefetch::{
+        compare_prefetcher, NoPrefetcher, Prefetcher, PrefetchingPager, SequentialPrefetcher,
+        StridePrefetcher, WorkingSetPrefetcher,
+    };
+    use crate::r#virtual::r#virtual::ReplacementPolicy;
+
+    #[test]
+    pub fn test_sequential_read_ahead() {
+        let references: Vec<usize> = (0..16).collect();
+
+        let comparison = compare_prefetcher(
+            ReplacementPolicy::Lru,
+            &references,
+            16,
+            4,
+            SequentialPrefetcher { degree: 2 },
+        );
+
+        assert_eq!(comparison.baseline_faults, 16);
+        assert_eq!(comparison.stats.demand_faults, 6);
+        assert_eq!(comparison.stats.prefetches_issued, 10);
+        assert_eq!(comparison.stats.useful, 10);
+        assert_eq!(comparison.stats.wasted, 0);
+        assert_eq!(comparison.faults_avoided(), 10);
+        assert_eq!(comparison.fault_reduction(), 0.625);
+    }
+
+    #[test]
+    pub fn test_stride_detection() {
+        let references: Vec<usize> = (0..11).map(|i| i * 3).collect();
+
+        let comparison = compare_prefetcher(
+            ReplacementPolicy::Lru,
+            &references,
+            32,
+            4,
+            StridePrefetcher::new(2),
+        );
+
+        assert_eq!(comparison.baseline_faults, 11);
+        assert_eq!(comparison.stats.demand_faults, 3);
+        assert_eq!(comparison.stats.prefetches_issued, 8);
+        assert_eq!(comparison.stats.useful, 8);
+        assert_eq!(comparison.stats.accuracy(), 1.0);
+    }
+
+    #[test]
+    pub fn test_wasted_prefetches() {
+        let comparison = compare_prefetcher(
+            ReplacementPolicy::Lru,
+            &[0, 10, 20, 30],
+            40,
+            3,
+            SequentialPrefetcher { degree: 2 },
+        );
+
+        assert_eq!(comparison.stats.demand_faults, 4);
+        assert_eq!(comparison.stats.prefetches_issued, 8);
+        assert_eq!(comparison.stats.useful, 0);
+        assert_eq!(comparison.stats.wasted, 6);
+        assert_eq!(comparison.faults_avoided(), 0);
+    }
+
+    #[test]
+    pub fn test_suspend_wastes_unused_prefetches() {
+        let mut pager = PrefetchingPager::new(
+            8,
+            4,
+            ReplacementPolicy::Fifo,
+            SequentialPrefetcher { degree: 2 },
+        );
+        pager.run(&[0, 1]);
+
+        pager.suspend();
+
+        assert_eq!(pager.stats.useful, 1);
+        assert_eq!(pager.stats.wasted, 1);
+        assert!(!pager.translator.page_table[0].is_valid);
+    }
+
+    fn faults_after_resume<P: Prefetcher>(prefetcher: P) -> usize {
+        let mut pager = PrefetchingPager::new(8, 4, ReplacementPolicy::Lru, prefetcher);
+        pager.run(&[4, 5, 0, 1, 2, 3, 0, 1]);
+        pager.suspend();
+        pager.resume();
+        let before = pager.stats.demand_faults;
+        pager.run(&[0, 1, 2, 3]);
+        pager.stats.demand_faults - before
+    }
+
+    #[test]
+    pub fn test_working_set_prepaging_on_resume() {
+        assert_eq!(faults_after_resume(NoPrefetcher), 4);
+        assert_eq!(faults_after_resume(WorkingSetPrefetcher::new(4)), 0);
+
+        let mut pager =
+            PrefetchingPager::new(8, 4, ReplacementPolicy::Lru, WorkingSetPrefetcher::new(4));
+        pager.run(&[4, 5, 0, 1, 2, 3, 0, 1]);
+        pager.suspend();
+        pager.resume();
+        pager.run(&[0, 1, 2, 3]);
+        assert_eq!(pager.stats.prefetches_issued, 4);
+        assert_eq!(pager.stats.useful, 4);
+        assert_eq!(pager.prefetcher.name(), "working-set");
+    }
+}
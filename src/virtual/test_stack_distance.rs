@@ -0,0 +1,105 @@
+#[cfg(test)]
+
+mod test_stack_distance {
+    use crate::r#virtual::r#virtual::{count_page_faults_lru, PTE};
+    use crate::r#virtual::stack_distance::{
+        miss_ratio_curve_to_csv, miss_ratio_curve_to_json, stack_distances, MissRatioPoint,
+        StackDistanceProfile,
+    };
+
+    #[test]
+    pub fn test_stack_distances() {
+        let reference_string = vec![0, 1, 2, 0, 0, 2, 3, 1];
+
+        let distances = stack_distances(&reference_string);
+
+        assert_eq!(
+            distances,
+            vec![None, None, None, Some(3), Some(1), Some(2), None, Some(4)]
+        );
+    }
+
+    #[test]
+    pub fn test_stack_distance_profile() {
+        let reference_string = vec![0, 1, 2, 0, 0, 2, 3, 1];
+
+        let profile = StackDistanceProfile::from_references(&reference_string);
+
+        assert_eq!(profile.reference_count, 8);
+        assert_eq!(profile.cold_misses, 4);
+        assert_eq!(profile.histogram, vec![1, 1, 1, 1]);
+        assert_eq!(profile.faults_for(0), 8);
+        assert_eq!(profile.faults_for(2), 6);
+        assert_eq!(profile.faults_for(4), 4);
+        assert_eq!(profile.faults_for(10), 4);
+    }
+
+    #[test]
+    pub fn test_stack_distance_matches_count_page_faults_lru() {
+        let reference_string = vec![0, 3, 2, 6, 3, 4, 5, 2, 4, 5, 6, 0, 3, 2, 1, 7, 3, 3, 6];
+        let profile = StackDistanceProfile::from_references(&reference_string);
+
+        for frames in 1..9 {
+            let mut page_table: Vec<PTE> = vec![PTE::default(); 8];
+            let mut frame_pool: Vec<i32> = (0..frames).collect();
+
+            let faults =
+                count_page_faults_lru(&mut page_table, reference_string.clone(), &mut frame_pool);
+
+            assert_eq!(profile.faults_for(frames as usize), faults);
+        }
+    }
+
+    #[test]
+    pub fn test_miss_ratio_curve() {
+        let reference_string = vec![0, 1, 2, 0, 0, 2, 3, 1];
+        let profile = StackDistanceProfile::from_references(&reference_string);
+
+        let curve = profile.miss_ratio_curve(3);
+
+        assert_eq!(
+            curve,
+            vec![
+                MissRatioPoint {
+                    frames: 1,
+                    faults: 7,
+                    miss_ratio: 0.875
+                },
+                MissRatioPoint {
+                    frames: 2,
+                    faults: 6,
+                    miss_ratio: 0.75
+                },
+                MissRatioPoint {
+                    frames: 3,
+                    faults: 5,
+                    miss_ratio: 0.625
+                },
+            ]
+        );
+    }
+
+    #[test]
+    pub fn test_miss_ratio_curve_to_csv() {
+        let profile = StackDistanceProfile::from_references(&[0, 1, 2, 0, 0, 2, 3, 1]);
+
+        let csv = miss_ratio_curve_to_csv(&profile.miss_ratio_curve(2));
+
+        assert_eq!(csv, "frames,faults,miss_ratio\n1,7,0.875\n2,6,0.75\n");
+    }
+
+    #[test]
+    pub fn test_miss_ratio_curve_to_json() {
+        let profile = StackDistanceProfile::from_references(&[0, 1, 2, 0, 0, 2, 3, 1]);
+        let curve = profile.miss_ratio_curve(2);
+
+        let json = miss_ratio_curve_to_json(&curve).unwrap();
+        let parsed: Vec<MissRatioPoint> = serde_json::from_str(&json).unwrap();
+
+        assert_eq!(
+            json,
+            "[{\"frames\":1,\"faults\":7,\"miss_ratio\":0.875},{\"frames\":2,\"faults\":6,\"miss_ratio\":0.75}]"
+        );
+        assert_eq!(parsed, curve);
+    }
+}
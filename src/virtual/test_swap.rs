@@ -0,0 +1,96 @@
+#[cfg(test)]
+
+mod test_swap {
+    use crate::r#virtual::cost::MemoryAccess;
+    use crate::r#virtual::r#virtual::ReplacementPolicy;
+    use crate::r#virtual::swap::{SwapDevice, SwappingPager};
+
+    #[test]
+    pub fn test_swap_device_slots() {
+        let mut swap = SwapDevice::new(2);
+
+        assert_eq!(swap.allocate(5), Some(0));
+        assert_eq!(swap.allocate(6), Some(1));
+        assert_eq!(swap.allocate(7), None);
+        assert!(swap.free(0));
+        assert!(!swap.free(0));
+        assert_eq!(swap.used_slots(), 1);
+        assert_eq!(swap.allocate(7), Some(0));
+        assert_eq!(swap.slots, vec![Some(7), Some(6)]);
+    }
+
+    #[test]
+    pub fn test_dirty_eviction_swaps_out() {
+        let mut pager = SwappingPager::new(4, 1, 4, ReplacementPolicy::Lru);
+
+        let stats = pager.run(&[
+            MemoryAccess::write(0),
+            MemoryAccess::read(1),
+            MemoryAccess::read(0),
+        ]);
+
+        assert_eq!(stats.swap_outs, 1);
+        assert_eq!(stats.swap_ins, 1);
+        assert_eq!(stats.zero_fill_faults, 2);
+        assert_eq!(pager.slot_of[0], Some(0));
+        assert_eq!(pager.slot_of[1], None);
+        assert_eq!(pager.swap.used_slots(), 1);
+    }
+
+    #[test]
+    pub fn test_swap_cache_avoids_rewrites() {
+        let mut pager = SwappingPager::new(4, 1, 4, ReplacementPolicy::Lru);
+
+        let stats = pager.run(&[
+            MemoryAccess::write(0),
+            MemoryAccess::read(1),
+            MemoryAccess::read(0),
+            MemoryAccess::read(1),
+            MemoryAccess::write(0),
+            MemoryAccess::read(1),
+        ]);
+
+        assert_eq!(stats.writes_avoided, 1);
+        assert_eq!(stats.swap_ins, 2);
+        assert_eq!(stats.swap_outs, 2);
+        assert_eq!(stats.slot_allocations, 1);
+        assert_eq!(stats.peak_slots_used, 1);
+    }
+
+    #[test]
+    pub fn test_out_of_swap() {
+        let mut pager = SwappingPager::new(4, 1, 1, ReplacementPolicy::Fifo);
+
+        let stats = pager.run(&[
+            MemoryAccess::write(0),
+            MemoryAccess::write(1),
+            MemoryAccess::write(2),
+        ]);
+
+        assert_eq!(stats.swap_outs, 1);
+        assert_eq!(stats.out_of_swap, 1);
+        assert_eq!(pager.slot_of[1], None);
+
+        assert!(pager.free_slot(0));
+        assert!(!pager.free_slot(0));
+        pager.access(MemoryAccess::write(3));
+        assert_eq!(pager.slot_of[2], Some(0));
+        assert_eq!(pager.stats.slot_frees, 1);
+    }
+
+    #[test]
+    pub fn test_swap_with_clock() {
+        let accesses: Vec<MemoryAccess> = [0, 1, 2, 3, 0, 1, 4, 0, 1, 2, 3, 4]
+            .iter()
+            .map(|&page| MemoryAccess::write(page))
+            .collect();
+        let mut pager = SwappingPager::new(5, 3, 5, ReplacementPolicy::Clock);
+
+        let stats = pager.run(&accesses);
+
+        assert_eq!(stats.zero_fill_faults, 5);
+        assert_eq!(stats.swap_outs, pager.translator.faults - 3);
+        assert_eq!(stats.swap_ins, pager.translator.faults - 5);
+        assert!(stats.peak_slots_used <= 5);
+    }
+}
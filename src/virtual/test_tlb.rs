@@ -0,0 +1,189 @@
+#[cfg(test)]
+
+mod test_tlb {
+    use crate::r#virtual::r#virtual::{process_page_access_lru, PTE};
+    use crate::r#virtual::tlb::{
+        effective_access_time, simulate_translation, AccessCosts, Tlb, TlbConfig, TlbReplacement,
+        TranslationRun,
+    };
+
+    fn fully_associative(entries: usize, replacement: TlbReplacement) -> TlbConfig {
+        TlbConfig {
+            entries,
+            associativity: entries,
+            replacement,
+            asid_tagged: true,
+        }
+    }
+
+    #[test]
+    pub fn test_tlb_lookup_and_insert() {
+        let mut tlb = Tlb::new(fully_associative(4, TlbReplacement::Lru));
+
+        assert_eq!(tlb.lookup(5), None);
+        tlb.insert(5, 20);
+        assert_eq!(tlb.lookup(5), Some(20));
+
+        assert_eq!(tlb.stats.hits, 1);
+        assert_eq!(tlb.stats.misses, 1);
+        assert_eq!(tlb.stats.hit_ratio(), 0.5);
+    }
+
+    #[test]
+    pub fn test_tlb_lru_replacement() {
+        let mut tlb = Tlb::new(fully_associative(2, TlbReplacement::Lru));
+
+        tlb.insert(1, 10);
+        tlb.insert(2, 20);
+        tlb.lookup(1);
+        tlb.insert(3, 30);
+
+        assert_eq!(tlb.lookup(1), Some(10));
+        assert_eq!(tlb.lookup(2), None);
+        assert_eq!(tlb.lookup(3), Some(30));
+        assert_eq!(tlb.stats.evictions, 1);
+    }
+
+    #[test]
+    pub fn test_tlb_fifo_replacement() {
+        let mut tlb = Tlb::new(fully_associative(2, TlbReplacement::Fifo));
+
+        tlb.insert(1, 10);
+        tlb.insert(2, 20);
+        tlb.lookup(1);
+        tlb.insert(3, 30);
+
+        assert_eq!(tlb.lookup(1), None);
+        assert_eq!(tlb.lookup(2), Some(20));
+    }
+
+    #[test]
+    pub fn test_tlb_random_replacement_is_reproducible() {
+        let mut first = Tlb::new(fully_associative(4, TlbReplacement::Random(9)));
+        let mut second = Tlb::new(fully_associative(4, TlbReplacement::Random(9)));
+
+        for page_number in 0..32 {
+            first.insert(page_number, page_number as i32);
+            second.insert(page_number, page_number as i32);
+        }
+
+        assert_eq!(first, second);
+        assert_eq!(first.occupancy(), 4);
+        assert_eq!(first.stats.evictions, 28);
+    }
+
+    #[test]
+    pub fn test_tlb_set_associative() {
+        let mut tlb = Tlb::new(TlbConfig {
+            entries: 4,
+            associativity: 2,
+            replacement: TlbReplacement::Lru,
+            asid_tagged: true,
+        });
+
+        tlb.insert(0, 10);
+        tlb.insert(2, 12);
+        tlb.insert(4, 14);
+
+        assert_eq!(tlb.sets.len(), 2);
+        assert_eq!(tlb.sets[0].len(), 2);
+        assert_eq!(tlb.sets[1].len(), 0);
+        assert_eq!(tlb.lookup(0), None);
+        assert_eq!(tlb.lookup(4), Some(14));
+    }
+
+    #[test]
+    pub fn test_tlb_context_switch_with_asid_tags() {
+        let mut tlb = Tlb::new(fully_associative(4, TlbReplacement::Lru));
+
+        tlb.insert(1, 10);
+        tlb.context_switch(7);
+        assert_eq!(tlb.lookup(1), None);
+        tlb.insert(1, 70);
+        tlb.context_switch(0);
+
+        assert_eq!(tlb.lookup(1), Some(10));
+        assert_eq!(tlb.occupancy(), 2);
+        assert_eq!(tlb.stats.flushes, 0);
+
+        tlb.flush_asid(7);
+        assert_eq!(tlb.occupancy(), 1);
+        assert_eq!(tlb.stats.flushes, 1);
+    }
+
+    #[test]
+    #[should_panic(expected = "multiple of the associativity")]
+    pub fn test_tlb_rejects_uneven_sets() {
+        Tlb::new(TlbConfig {
+            entries: 6,
+            associativity: 4,
+            replacement: TlbReplacement::Lru,
+            asid_tagged: false,
+        });
+    }
+
+    #[test]
+    pub fn test_tlb_context_switch_without_asid_tags_flushes() {
+        let mut tlb = Tlb::new(TlbConfig {
+            asid_tagged: false,
+            ..fully_associative(4, TlbReplacement::Lru)
+        });
+
+        tlb.insert(1, 10);
+        tlb.context_switch(7);
+        tlb.context_switch(7);
+
+        assert_eq!(tlb.occupancy(), 0);
+        assert_eq!(tlb.stats.flushes, 1);
+        assert_eq!(tlb.current_asid, 7);
+    }
+
+    #[test]
+    pub fn test_effective_access_time() {
+        let costs = AccessCosts {
+            tlb_lookup_ns: 1.0,
+            memory_access_ns: 100.0,
+            page_table_levels: 2,
+            page_fault_ns: 8_000_000.0,
+        };
+
+        assert!((effective_access_time(&costs, 0.8, 0.0) - 141.0).abs() < 1e-9);
+        assert!((effective_access_time(&costs, 0.8, 0.001) - 8140.859).abs() < 1e-6);
+    }
+
+    #[test]
+    pub fn test_simulate_translation() {
+        let mut tlb = Tlb::new(fully_associative(2, TlbReplacement::Lru));
+        let mut page_table: Vec<PTE> = vec![PTE::default(); 4];
+        let mut frame_pool: Vec<i32> = vec![0, 1];
+
+        let run = simulate_translation(
+            &mut tlb,
+            &mut page_table,
+            vec![0, 1, 0, 2, 0, 1],
+            &mut frame_pool,
+            process_page_access_lru,
+        );
+
+        assert_eq!(
+            run,
+            TranslationRun {
+                accesses: 6,
+                tlb_hits: 2,
+                tlb_misses: 4,
+                page_faults: 4,
+            }
+        );
+        assert_eq!(tlb.occupancy(), 2);
+        assert_eq!(tlb.lookup(2), None);
+
+        let costs = AccessCosts {
+            tlb_lookup_ns: 1.0,
+            memory_access_ns: 100.0,
+            page_table_levels: 1,
+            page_fault_ns: 1000.0,
+        };
+        let expected = effective_access_time(&costs, 2.0 / 6.0, 4.0 / 6.0);
+        assert_eq!(run.effective_access_time(&costs), expected);
+    }
+}
@@ -0,0 +1,226 @@
+#[cfg(test)]
+
+mod test_trace {
+    use crate::r#virtual::address::AddressTranslator;
+    use crate::r#virtual::r#virtual::{count_page_faults_lru, ReplacementPolicy, PTE};
+    use crate::r#virtual::trace::{
+        open_trace, run_trace, AccessKind, TraceError, TraceFormat, TraceReader, TraceRecord,
+    };
+    use std::io::{BufRead, BufReader, Cursor, Read};
+
+    fn read_all(text: &str, format: TraceFormat) -> Vec<TraceRecord> {
+        TraceReader::new(Cursor::new(text), format)
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap()
+    }
+
+    #[test]
+    pub fn test_plain_pages() {
+        let records = read_all("0 3 2\n6\n\n  3 4 \n", TraceFormat::PlainPages);
+
+        let pages: Vec<u64> = records.iter().map(|r| r.address).collect();
+        assert_eq!(pages, vec![0, 3, 2, 6, 3, 4]);
+        assert!(records.iter().all(|r| r.kind == AccessKind::Read));
+    }
+
+    #[test]
+    pub fn test_dinero() {
+        let records = read_all("0 1000\n1 0x2004\n2 3ff8\n4 0\n", TraceFormat::Dinero);
+
+        assert_eq!(
+            records,
+            vec![
+                TraceRecord {
+                    address: 0x1000,
+                    kind: AccessKind::Read
+                },
+                TraceRecord {
+                    address: 0x2004,
+                    kind: AccessKind::Write
+                },
+                TraceRecord {
+                    address: 0x3ff8,
+                    kind: AccessKind::InstructionFetch
+                },
+            ]
+        );
+        assert!(records[1].to_memory_access(0x1000).is_write);
+        assert_eq!(records[1].page_number(0x1000), 2);
+    }
+
+    #[test]
+    pub fn test_lackey() {
+        let text = "==1234== Lackey, an example Valgrind tool\n\
+                    I  04016f18,3\n \
+                    S 7ff000ba8,8\n \
+                    L 04222cac,8\n \
+                    M 0421ee90,4\n\
+                    ==1234== \n";
+
+        let records = read_all(text, TraceFormat::Lackey);
+
+        let kinds: Vec<AccessKind> = records.iter().map(|r| r.kind).collect();
+        assert_eq!(
+            kinds,
+            vec![
+                AccessKind::InstructionFetch,
+                AccessKind::Write,
+                AccessKind::Read,
+                AccessKind::Write
+            ]
+        );
+        assert_eq!(records[1].address, 0x7ff000ba8);
+    }
+
+    #[test]
+    pub fn test_parse_errors_report_line() {
+        let mut reader = TraceReader::new(
+            Cursor::new("0 1000\n7 1000\n1 zz\n0 10\n"),
+            TraceFormat::Dinero,
+        );
+
+        assert!(reader.next().unwrap().is_ok());
+        match reader.next().unwrap() {
+            Err(TraceError::Parse { line, .. }) => assert_eq!(line, 2),
+            other => panic!("unexpected {:?}", other),
+        }
+        let error = reader.next().unwrap().unwrap_err();
+        assert_eq!(error.to_string(), "line 3: bad address `zz`");
+        assert_eq!(reader.next().unwrap().unwrap().address, 0x10);
+        assert!(reader.next().is_none());
+    }
+
+    // Hands out the trace a few bytes at a time, like a file far larger
+    // than any buffer.
+    struct Trickle {
+        data: Vec<u8>,
+        position: usize,
+    }
+
+    impl Read for Trickle {
+        fn read(&mut self, buffer: &mut [u8]) -> std::io::Result<usize> {
+            let end = std::cmp::min(self.position + 3, self.data.len());
+            let count = std::cmp::min(end - self.position, buffer.len());
+            buffer[..count].copy_from_slice(&self.data[self.position..self.position + count]);
+            self.position += count;
+            Ok(count)
+        }
+    }
+
+    // A single line that never ends; only a reader that tokenizes as it
+    // goes can return records from it.
+    struct EndlessLine {
+        next_page: u8,
+    }
+
+    impl Read for EndlessLine {
+        fn read(&mut self, buffer: &mut [u8]) -> std::io::Result<usize> {
+            let mut count = 0;
+            while count + 2 <= buffer.len() {
+                buffer[count] = b'0' + self.next_page;
+                buffer[count + 1] = b' ';
+                self.next_page = (self.next_page + 1) % 10;
+                count += 2;
+            }
+            Ok(count)
+        }
+    }
+
+    #[test]
+    pub fn test_plain_pages_stream_within_a_line() {
+        let reader = TraceReader::new(
+            BufReader::with_capacity(8, EndlessLine { next_page: 0 }),
+            TraceFormat::PlainPages,
+        );
+
+        let pages: Vec<u64> = reader.take(25).map(|r| r.unwrap().address).collect();
+
+        let expected: Vec<u64> = (0..25).map(|i| i % 10).collect();
+        assert_eq!(pages, expected);
+    }
+
+    #[test]
+    pub fn test_plain_pages_errors_report_line() {
+        let mut reader = TraceReader::new(Cursor::new("1 2\n\n3 x4 5\n"), TraceFormat::PlainPages);
+
+        assert_eq!(reader.next().unwrap().unwrap().address, 1);
+        assert_eq!(reader.next().unwrap().unwrap().address, 2);
+        assert_eq!(reader.next().unwrap().unwrap().address, 3);
+        let error = reader.next().unwrap().unwrap_err();
+        assert_eq!(error.to_string(), "line 3: bad page number `x4`");
+        assert_eq!(reader.next().unwrap().unwrap().address, 5);
+        assert!(reader.next().is_none());
+        assert_eq!(reader.line_number(), 3);
+    }
+
+    #[test]
+    pub fn test_dinero_labels() {
+        let mut reader =
+            TraceReader::new(Cursor::new("3 10\n4 20\n5 30\n1 40\n"), TraceFormat::Dinero);
+
+        let error = reader.next().unwrap().unwrap_err();
+        assert_eq!(error.to_string(), "line 3: bad label `5`");
+        assert_eq!(
+            reader.next().unwrap().unwrap(),
+            TraceRecord {
+                address: 0x40,
+                kind: AccessKind::Write
+            }
+        );
+        assert!(reader.next().is_none());
+    }
+
+    #[test]
+    pub fn test_hex_prefix_accepted_once() {
+        let mut reader =
+            TraceReader::new(Cursor::new("0 0X1f\n0 0x0x1f\n0 1f\n"), TraceFormat::Dinero);
+
+        assert_eq!(reader.next().unwrap().unwrap().address, 0x1f);
+        let error = reader.next().unwrap().unwrap_err();
+        assert_eq!(error.to_string(), "line 2: bad address `0x0x1f`");
+        assert_eq!(reader.next().unwrap().unwrap().address, 0x1f);
+    }
+
+    #[test]
+    pub fn test_streaming_run_matches_count_page_faults() {
+        let references = vec![0, 3, 2, 6, 3, 4, 5, 2, 4, 5, 6];
+        let text: Vec<String> = references.iter().map(|r| r.to_string()).collect();
+        let source: Box<dyn BufRead> = Box::new(BufReader::with_capacity(
+            4,
+            Trickle {
+                data: text.join("\n").into_bytes(),
+                position: 0,
+            },
+        ));
+        let mut translator = AddressTranslator::new(1, 7, 3, ReplacementPolicy::Lru);
+
+        let run = run_trace(
+            &mut translator,
+            TraceReader::new(source, TraceFormat::PlainPages),
+        )
+        .unwrap();
+
+        let mut page_table = vec![PTE::default(); 7];
+        let mut frame_pool = vec![0, 1, 2];
+        let faults = count_page_faults_lru(&mut page_table, references, &mut frame_pool);
+        assert_eq!(run.records, 11);
+        assert_eq!(run.faults as i32, faults);
+        assert_eq!(run.out_of_range, 0);
+    }
+
+    #[test]
+    pub fn test_open_trace_file() {
+        let path = std::env::temp_dir().join("mos_rust_test_trace.din");
+        std::fs::write(&path, "0 0\n1 1000\n0 0\n").unwrap();
+        let mut translator = AddressTranslator::new(0x1000, 4, 2, ReplacementPolicy::Fifo);
+
+        let run = run_trace(
+            &mut translator,
+            open_trace(&path, TraceFormat::Dinero).unwrap(),
+        );
+        std::fs::remove_file(&path).unwrap();
+
+        assert_eq!(run.unwrap().hits, 1);
+        assert!(open_trace(&path, TraceFormat::Dinero).is_err());
+    }
+}
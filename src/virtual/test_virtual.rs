@@ -5,6 +5,17 @@ mod test_virtual {
         count_page_faults_fifo, count_page_faults_lru, process_page_access_fifo,
         process_page_access_lru, PTE, process_page_access_lfu, count_page_faults_lfu,
     };
+    use crate::r#virtual::r#virtual::{
+        count_page_faults_2q, count_page_faults_arc, count_page_faults_arc_with_state,
+        count_page_faults_clock, count_page_faults_clock_pro, count_page_faults_lirs,
+        process_page_access_2q, process_page_access_arc, process_page_access_clock,
+        process_page_access_clock_pro, process_page_access_lirs, ArcState, ClockProState,
+        ClockState, LirsState, LirsStatus, TwoQState, count_page_faults_aging,
+        process_page_access_aging, AgingConfig, AgingState, count_page_faults_mru,
+        count_page_faults_random, process_page_access_mru, process_page_access_random,
+        ReplacementPolicy, SeededRng, count_page_faults_opt, process_references_opt,
+        select_victim,
+    };
 
     fn set_pte(
         is_valid: bool,
@@ -19,6 +30,7 @@ mod test_virtual {
             arrival_timestamp,
             last_access_timestamp,
             reference_count,
+            ..PTE::default()
         };
         pte
     }
@@ -606,4 +618,738 @@ mod test_virtual {
         assert_eq!(faults, 5);
     }
 
+    #[test]
+    pub fn test_process_page_access_arc_hit_moves_page_to_t2() {
+        let mut page_table: Vec<PTE> = Vec::new();
+        let mut frame_pool: Vec<i32> = Vec::new();
+
+        for _i in 0..8 {
+            page_table.push(PTE::default());
+        }
+
+        page_table[2] = set_pte(true, 10, 3, 3, 1);
+        page_table[5] = set_pte(true, 20, 2, 4, 2);
+        page_table[7] = set_pte(true, 30, 1, 1, 1);
+        let mut arc_state = ArcState::new(&page_table, &frame_pool);
+
+        let frame =
+            process_page_access_arc(&mut arc_state, &mut page_table, 2, &mut frame_pool, 12);
+
+        assert_eq!(frame, 10);
+        assert_eq!(page_table[2], set_pte(true, 10, 3, 12, 2));
+        assert_eq!(arc_state.capacity, 3);
+        assert_eq!(arc_state.t1, vec![7, 5]);
+        assert_eq!(arc_state.t2, vec![2]);
+        assert_eq!(arc_state.p_history, vec![0]);
+    }
+
+    #[test]
+    pub fn test_process_page_access_arc_page_not_in_memory_no_frame_available() {
+        let mut page_table: Vec<PTE> = Vec::new();
+        let mut frame_pool: Vec<i32> = Vec::new();
+
+        for _i in 0..8 {
+            page_table.push(PTE::default());
+        }
+
+        page_table[2] = set_pte(true, 10, 3, 3, 1);
+        page_table[5] = set_pte(true, 20, 2, 4, 2);
+        page_table[7] = set_pte(true, 30, 1, 1, 1);
+        let mut arc_state = ArcState::new(&page_table, &frame_pool);
+
+        let frame =
+            process_page_access_arc(&mut arc_state, &mut page_table, 0, &mut frame_pool, 12);
+
+        let mut expected_page_table: Vec<PTE> = Vec::new();
+        for _i in 0..8 {
+            expected_page_table.push(PTE::default());
+        }
+        expected_page_table[0] = set_pte(true, 30, 12, 12, 1);
+        expected_page_table[2] = set_pte(true, 10, 3, 3, 1);
+        expected_page_table[5] = set_pte(true, 20, 2, 4, 2);
+
+        assert_eq!(page_table, expected_page_table);
+        assert_eq!(frame, 30);
+        assert_eq!(arc_state.t1, vec![2, 5, 0]);
+        assert_eq!(arc_state.b1.len(), 0);
+    }
+
+    #[test]
+    pub fn test_count_page_faults_arc() {
+        let mut page_table: Vec<PTE> = Vec::new();
+
+        for _i in 0..8 {
+            page_table.push(PTE::default());
+        }
+
+        let reference_string = vec![0, 3, 2, 6, 3, 4, 5, 2, 4, 5, 6];
+        let mut frame_pool: Vec<i32> = vec![0, 1, 2];
+
+        let faults = count_page_faults_arc(&mut page_table, reference_string, &mut frame_pool);
+
+        assert_eq!(faults, 10);
+    }
+
+    #[test]
+    pub fn test_count_page_faults_arc_keeps_frequent_page() {
+        let reference_string = vec![0, 0, 1, 2, 0];
+
+        let mut page_table: Vec<PTE> = vec![PTE::default(); 8];
+        let mut frame_pool: Vec<i32> = vec![0, 1];
+        let arc_faults =
+            count_page_faults_arc(&mut page_table, reference_string.clone(), &mut frame_pool);
+
+        let mut page_table: Vec<PTE> = vec![PTE::default(); 8];
+        let mut frame_pool: Vec<i32> = vec![0, 1];
+        let lru_faults = count_page_faults_lru(&mut page_table, reference_string, &mut frame_pool);
+
+        assert_eq!(arc_faults, 3);
+        assert_eq!(lru_faults, 4);
+    }
+
+    #[test]
+    pub fn test_count_page_faults_arc_adapts_target() {
+        let mut page_table: Vec<PTE> = vec![PTE::default(); 8];
+        let mut frame_pool: Vec<i32> = vec![0, 1];
+        let mut arc_state = ArcState::new(&page_table, &frame_pool);
+
+        let reference_string = vec![0, 0, 1, 2, 0, 1, 0];
+        let faults = count_page_faults_arc_with_state(
+            &mut arc_state,
+            &mut page_table,
+            reference_string,
+            &mut frame_pool,
+        );
+
+        assert_eq!(faults, 5);
+        assert_eq!(arc_state.p_history, vec![0, 0, 0, 0, 0, 1, 0]);
+        assert_eq!(arc_state.t2, vec![1, 0]);
+        assert_eq!(arc_state.b1, vec![2]);
+    }
+
+    #[test]
+    pub fn test_count_page_faults_arc_preloaded_pages() {
+        let mut page_table: Vec<PTE> = vec![PTE::default(); 8];
+
+        page_table[2] = set_pte(true, 10, 3, 3, 1);
+        page_table[5] = set_pte(true, 20, 2, 4, 2);
+        page_table[7] = set_pte(true, 30, 1, 1, 1);
+
+        let reference_string = vec![2, 5, 7, 2, 0];
+        let mut frame_pool: Vec<i32> = Vec::new();
+
+        let faults = count_page_faults_arc(&mut page_table, reference_string, &mut frame_pool);
+
+        assert_eq!(faults, 1);
+    }
+
+    fn scan_reference_string() -> Vec<i32> {
+        vec![
+            0, 1, 2, 10, 11, 12, 0, 1, 2, 13, 14, 15, 0, 1, 2, 16, 17, 18, 0, 1, 2, 19, 20, 21,
+        ]
+    }
+
+    #[test]
+    pub fn test_process_page_access_lirs_promotes_non_resident_hir() {
+        let mut page_table: Vec<PTE> = vec![PTE::default(); 8];
+        let mut frame_pool: Vec<i32> = vec![0, 1, 2];
+        let mut lirs_state = LirsState::new(&page_table, &frame_pool);
+
+        let mut current_timestamp = 1;
+        for page_number in [0, 1, 2, 0, 3] {
+            process_page_access_lirs(
+                &mut lirs_state,
+                &mut page_table,
+                page_number,
+                &mut frame_pool,
+                current_timestamp,
+            );
+            current_timestamp += 1;
+        }
+
+        assert_eq!(lirs_state.lir_capacity, 2);
+        assert_eq!(lirs_state.stack, vec![1, 2, 0, 3]);
+        assert_eq!(lirs_state.queue, vec![3]);
+        assert_eq!(lirs_state.status[&2], LirsStatus::NonResidentHir);
+        assert_eq!(page_table[2], PTE::default());
+
+        let frame = process_page_access_lirs(
+            &mut lirs_state,
+            &mut page_table,
+            2,
+            &mut frame_pool,
+            current_timestamp,
+        );
+
+        assert_eq!(frame, page_table[2].frame_number);
+        assert_eq!(page_table[3], PTE::default());
+        assert_eq!(lirs_state.stack, vec![0, 3, 2]);
+        assert_eq!(lirs_state.queue, vec![1]);
+        assert_eq!(lirs_state.status[&1], LirsStatus::ResidentHir);
+        assert_eq!(lirs_state.status[&2], LirsStatus::Lir);
+        assert_eq!(lirs_state.status[&3], LirsStatus::NonResidentHir);
+    }
+
+    #[test]
+    pub fn test_count_page_faults_lirs() {
+        let mut page_table: Vec<PTE> = vec![PTE::default(); 8];
+
+        let reference_string = vec![0, 3, 2, 6, 3, 4, 5, 2, 4, 5, 6];
+        let mut frame_pool: Vec<i32> = vec![0, 1, 2];
+
+        let faults = count_page_faults_lirs(&mut page_table, reference_string, &mut frame_pool);
+
+        assert_eq!(faults, 10);
+    }
+
+    #[test]
+    pub fn test_count_page_faults_lirs_case2() {
+        let mut page_table: Vec<PTE> = vec![PTE::default(); 8];
+
+        page_table[2] = set_pte(true, 10, 3, 3, 1);
+        page_table[5] = set_pte(true, 20, 2, 4, 2);
+        page_table[7] = set_pte(true, 30, 1, 1, 1);
+
+        let reference_string = vec![2, 5, 0, 7, 4, 2, 3, 5, 1, 2, 6, 0];
+        let mut frame_pool: Vec<i32> = Vec::new();
+
+        let faults = count_page_faults_lirs(&mut page_table, reference_string, &mut frame_pool);
+
+        assert_eq!(faults, 7);
+    }
+
+    #[test]
+    pub fn test_count_page_faults_lirs_scan_resistance() {
+        let mut page_table: Vec<PTE> = vec![PTE::default(); 24];
+        let mut frame_pool: Vec<i32> = vec![0, 1, 2, 3, 4];
+        let lirs_faults =
+            count_page_faults_lirs(&mut page_table, scan_reference_string(), &mut frame_pool);
+
+        let mut page_table: Vec<PTE> = vec![PTE::default(); 24];
+        let mut frame_pool: Vec<i32> = vec![0, 1, 2, 3, 4];
+        let lru_faults =
+            count_page_faults_lru(&mut page_table, scan_reference_string(), &mut frame_pool);
+
+        assert_eq!(lru_faults, 24);
+        assert_eq!(lirs_faults, 15);
+    }
+
+    #[test]
+    pub fn test_process_page_access_2q_promotes_from_a1out() {
+        let mut page_table: Vec<PTE> = vec![PTE::default(); 8];
+        let mut frame_pool: Vec<i32> = vec![0, 1, 2];
+        let mut two_q_state = TwoQState::new(&page_table, &frame_pool);
+
+        let mut current_timestamp = 1;
+        for page_number in [0, 1, 2, 0, 3] {
+            process_page_access_2q(
+                &mut two_q_state,
+                &mut page_table,
+                page_number,
+                &mut frame_pool,
+                current_timestamp,
+            );
+            current_timestamp += 1;
+        }
+
+        assert_eq!(two_q_state.kin, 1);
+        assert_eq!(two_q_state.kout, 1);
+        assert_eq!(two_q_state.a1in, vec![1, 2, 3]);
+        assert_eq!(two_q_state.a1out, vec![0]);
+        assert_eq!(page_table[0], PTE::default());
+
+        let frame = process_page_access_2q(
+            &mut two_q_state,
+            &mut page_table,
+            0,
+            &mut frame_pool,
+            current_timestamp,
+        );
+
+        assert_eq!(frame, page_table[0].frame_number);
+        assert_eq!(page_table[1], PTE::default());
+        assert_eq!(two_q_state.a1in, vec![2, 3]);
+        assert_eq!(two_q_state.a1out, vec![1]);
+        assert_eq!(two_q_state.am, vec![0]);
+    }
+
+    #[test]
+    pub fn test_count_page_faults_2q() {
+        let mut page_table: Vec<PTE> = vec![PTE::default(); 8];
+
+        let reference_string = vec![0, 3, 2, 6, 3, 4, 5, 2, 4, 5, 6];
+        let mut frame_pool: Vec<i32> = vec![0, 1, 2];
+
+        let faults = count_page_faults_2q(&mut page_table, reference_string, &mut frame_pool);
+
+        assert_eq!(faults, 8);
+    }
+
+    #[test]
+    pub fn test_count_page_faults_2q_case2() {
+        let mut page_table: Vec<PTE> = vec![PTE::default(); 8];
+
+        page_table[2] = set_pte(true, 10, 3, 3, 1);
+        page_table[5] = set_pte(true, 20, 2, 4, 2);
+        page_table[7] = set_pte(true, 30, 1, 1, 1);
+
+        let reference_string = vec![2, 5, 0, 7, 4, 2, 3, 5, 1, 2, 6, 0];
+        let mut frame_pool: Vec<i32> = Vec::new();
+
+        let faults = count_page_faults_2q(&mut page_table, reference_string, &mut frame_pool);
+
+        assert_eq!(faults, 9);
+    }
+
+    #[test]
+    pub fn test_count_page_faults_2q_scan_resistance() {
+        let mut page_table: Vec<PTE> = vec![PTE::default(); 24];
+        let mut frame_pool: Vec<i32> = vec![0, 1, 2, 3, 4];
+        let two_q_faults =
+            count_page_faults_2q(&mut page_table, scan_reference_string(), &mut frame_pool);
+
+        let mut page_table: Vec<PTE> = vec![PTE::default(); 24];
+        let mut frame_pool: Vec<i32> = vec![0, 1, 2, 3, 4];
+        let lru_faults =
+            count_page_faults_lru(&mut page_table, scan_reference_string(), &mut frame_pool);
+
+        assert_eq!(lru_faults, 24);
+        assert_eq!(two_q_faults, 18);
+    }
+
+    #[test]
+    pub fn test_process_page_access_clock_page_not_in_memory_no_frame_available() {
+        let mut page_table: Vec<PTE> = vec![PTE::default(); 8];
+        let mut frame_pool: Vec<i32> = Vec::new();
+
+        page_table[2] = set_pte(true, 10, 3, 3, 1);
+        page_table[5] = set_pte(true, 20, 2, 4, 2);
+        page_table[7] = set_pte(true, 30, 1, 1, 1);
+        let mut clock_state = ClockState::new(&page_table);
+        clock_state.referenced = vec![true, false, true];
+
+        let frame =
+            process_page_access_clock(&mut clock_state, &mut page_table, 0, &mut frame_pool, 12);
+
+        let mut expected_page_table: Vec<PTE> = vec![PTE::default(); 8];
+        expected_page_table[0] = set_pte(true, 20, 12, 12, 1);
+        expected_page_table[2] = set_pte(true, 10, 3, 3, 1);
+        expected_page_table[7] = set_pte(true, 30, 1, 1, 1);
+
+        assert_eq!(page_table, expected_page_table);
+        assert_eq!(frame, 20);
+        assert_eq!(clock_state.ring, vec![7, 0, 2]);
+        assert_eq!(clock_state.referenced, vec![false, true, true]);
+        assert_eq!(clock_state.hand, 2);
+    }
+
+    #[test]
+    pub fn test_count_page_faults_clock() {
+        let mut page_table: Vec<PTE> = vec![PTE::default(); 8];
+
+        let reference_string = vec![0, 3, 2, 6, 3, 4, 5, 2, 4, 5, 6];
+        let mut frame_pool: Vec<i32> = vec![0, 1, 2];
+
+        let faults = count_page_faults_clock(&mut page_table, reference_string, &mut frame_pool);
+
+        assert_eq!(faults, 8);
+    }
+
+    #[test]
+    pub fn test_process_page_access_clock_pro_test_period_hit_promotes_page() {
+        let mut page_table: Vec<PTE> = vec![PTE::default(); 8];
+        let mut frame_pool: Vec<i32> = vec![0, 1, 2];
+        let mut clock_pro_state = ClockProState::new(&page_table, &frame_pool);
+
+        let mut current_timestamp = 1;
+        for page_number in [0, 1, 2, 3] {
+            process_page_access_clock_pro(
+                &mut clock_pro_state,
+                &mut page_table,
+                page_number,
+                &mut frame_pool,
+                current_timestamp,
+            );
+            current_timestamp += 1;
+        }
+
+        assert_eq!(clock_pro_state.hot_count, 2);
+        assert_eq!(clock_pro_state.cold_count, 1);
+        assert_eq!(clock_pro_state.non_resident_count, 1);
+        assert_eq!(clock_pro_state.cold_target, 1);
+        assert_eq!(page_table[2], PTE::default());
+
+        process_page_access_clock_pro(
+            &mut clock_pro_state,
+            &mut page_table,
+            2,
+            &mut frame_pool,
+            current_timestamp,
+        );
+
+        let hot_pages: Vec<usize> = clock_pro_state
+            .entries
+            .iter()
+            .filter(|e| e.is_hot)
+            .map(|e| e.page_number)
+            .collect();
+        assert_eq!(hot_pages, vec![2]);
+        assert_eq!(clock_pro_state.cold_target, 2);
+        assert_eq!(clock_pro_state.cold_count, 2);
+        assert_eq!(clock_pro_state.non_resident_count, 1);
+        assert_eq!(page_table[3], PTE::default());
+        assert!(page_table[0].is_valid && page_table[1].is_valid && page_table[2].is_valid);
+    }
+
+    #[test]
+    pub fn test_count_page_faults_clock_pro() {
+        let mut page_table: Vec<PTE> = vec![PTE::default(); 8];
+
+        let reference_string = vec![0, 3, 2, 6, 3, 4, 5, 2, 4, 5, 6];
+        let mut frame_pool: Vec<i32> = vec![0, 1, 2];
+
+        let faults =
+            count_page_faults_clock_pro(&mut page_table, reference_string, &mut frame_pool);
+
+        assert_eq!(faults, 10);
+    }
+
+    #[test]
+    pub fn test_count_page_faults_clock_pro_scan_resistance() {
+        let mut page_table: Vec<PTE> = vec![PTE::default(); 24];
+        let mut frame_pool: Vec<i32> = vec![0, 1, 2, 3, 4];
+        let clock_pro_faults =
+            count_page_faults_clock_pro(&mut page_table, scan_reference_string(), &mut frame_pool);
+
+        let mut page_table: Vec<PTE> = vec![PTE::default(); 24];
+        let mut frame_pool: Vec<i32> = vec![0, 1, 2, 3, 4];
+        let clock_faults =
+            count_page_faults_clock(&mut page_table, scan_reference_string(), &mut frame_pool);
+
+        assert_eq!(clock_faults, 24);
+        assert_eq!(clock_pro_faults, 15);
+    }
+
+    #[test]
+    pub fn test_process_page_access_aging_shifts_reference_bits() {
+        let mut page_table: Vec<PTE> = vec![PTE::default(); 8];
+        let mut frame_pool: Vec<i32> = vec![0, 1];
+        let config = AgingConfig {
+            counter_bits: 4,
+            tick_interval: 2,
+        };
+        let mut aging_state = AgingState::new(&page_table, config);
+
+        let mut current_timestamp = 1;
+        for page_number in [0, 1, 0] {
+            process_page_access_aging(
+                &mut aging_state,
+                &mut page_table,
+                page_number,
+                &mut frame_pool,
+                current_timestamp,
+            );
+            current_timestamp += 1;
+        }
+
+        assert_eq!(aging_state.counters[0], 0b1000);
+        assert_eq!(aging_state.counters[1], 0b1000);
+        assert!(aging_state.referenced[0]);
+        assert!(!aging_state.referenced[1]);
+
+        let frame = process_page_access_aging(
+            &mut aging_state,
+            &mut page_table,
+            2,
+            &mut frame_pool,
+            current_timestamp,
+        );
+
+        assert_eq!(page_table[1], PTE::default());
+        assert_eq!(page_table[2], set_pte(true, frame, 4, 4, 1));
+        assert_eq!(aging_state.counters[0], 0b1100);
+        assert_eq!(aging_state.counters[1], 0);
+        assert_eq!(aging_state.counters[2], 0b1000);
+        assert_eq!(aging_state.accesses_since_tick, 0);
+    }
+
+    #[test]
+    pub fn test_count_page_faults_aging() {
+        let mut page_table: Vec<PTE> = vec![PTE::default(); 8];
+
+        let reference_string = vec![0, 3, 2, 6, 3, 4, 5, 2, 4, 5, 6];
+        let mut frame_pool: Vec<i32> = vec![0, 1, 2];
+
+        let faults = count_page_faults_aging(
+            &mut page_table,
+            reference_string,
+            &mut frame_pool,
+            AgingConfig::default(),
+        );
+
+        assert_eq!(faults, 8);
+    }
+
+    #[test]
+    pub fn test_count_page_faults_aging_decays_old_frequency() {
+        let reference_string = vec![0, 0, 0, 0, 0, 1, 2, 3, 1, 2, 3, 1, 2, 3];
+
+        let mut page_table: Vec<PTE> = vec![PTE::default(); 8];
+        let mut frame_pool: Vec<i32> = vec![0, 1, 2];
+        let aging_faults = count_page_faults_aging(
+            &mut page_table,
+            reference_string.clone(),
+            &mut frame_pool,
+            AgingConfig::default(),
+        );
+
+        let mut page_table: Vec<PTE> = vec![PTE::default(); 8];
+        let mut frame_pool: Vec<i32> = vec![0, 1, 2];
+        let lfu_faults = count_page_faults_lfu(&mut page_table, reference_string, &mut frame_pool);
+
+        assert_eq!(aging_faults, 4);
+        assert_eq!(lfu_faults, 10);
+    }
+
+    #[test]
+    pub fn test_seeded_rng_matches_splitmix64() {
+        let mut rng = SeededRng::new(0);
+
+        assert_eq!(rng.next_u64(), 0xE220_A839_7B1D_CDAF);
+        assert_eq!(SeededRng::new(42), SeededRng::new(42));
+    }
+
+    #[test]
+    pub fn test_seeded_rng_next_below_is_uniform() {
+        let mut rng = SeededRng::new(5);
+        let mut counts = [0; 6];
+
+        for _i in 0..60000 {
+            counts[rng.next_below(6)] += 1;
+        }
+
+        assert!(counts.iter().all(|&count| count > 9500 && count < 10500));
+        assert_eq!(rng.next_below(1), 0);
+    }
+
+    #[test]
+    #[should_panic(expected = "non-zero bound")]
+    pub fn test_seeded_rng_next_below_zero() {
+        SeededRng::new(5).next_below(0);
+    }
+
+    #[test]
+    pub fn test_process_page_access_random_page_not_in_memory_no_frame_available() {
+        let mut page_table: Vec<PTE> = vec![PTE::default(); 8];
+        let mut frame_pool: Vec<i32> = Vec::new();
+
+        page_table[2] = set_pte(true, 10, 3, 3, 1);
+        page_table[5] = set_pte(true, 20, 2, 4, 2);
+        page_table[7] = set_pte(true, 30, 1, 1, 1);
+        let mut rng = SeededRng::new(42);
+
+        let frame = process_page_access_random(&mut rng, &mut page_table, 0, &mut frame_pool, 12);
+
+        let mut expected_page_table: Vec<PTE> = vec![PTE::default(); 8];
+        expected_page_table[0] = set_pte(true, 30, 12, 12, 1);
+        expected_page_table[2] = set_pte(true, 10, 3, 3, 1);
+        expected_page_table[5] = set_pte(true, 20, 2, 4, 2);
+
+        assert_eq!(page_table, expected_page_table);
+        assert_eq!(frame, 30);
+    }
+
+    #[test]
+    pub fn test_count_page_faults_random_is_reproducible() {
+        let reference_string = vec![0, 3, 2, 6, 3, 4, 5, 2, 4, 5, 6, 0, 3, 2, 6];
+        let mut results: Vec<i32> = Vec::new();
+
+        for _i in 0..2 {
+            let mut page_table: Vec<PTE> = vec![PTE::default(); 8];
+            let mut frame_pool: Vec<i32> = vec![0, 1, 2];
+            results.push(count_page_faults_random(
+                &mut page_table,
+                reference_string.clone(),
+                &mut frame_pool,
+                7,
+            ));
+        }
+
+        assert_eq!(results[0], results[1]);
+    }
+
+    #[test]
+    pub fn test_count_page_faults_random() {
+        let mut page_table: Vec<PTE> = vec![PTE::default(); 8];
+
+        let reference_string = vec![0, 3, 2, 6, 3, 4, 5, 2, 4, 5, 6];
+        let mut frame_pool: Vec<i32> = vec![0, 1, 2];
+
+        let faults =
+            count_page_faults_random(&mut page_table, reference_string, &mut frame_pool, 42);
+
+        assert_eq!(faults, 10);
+    }
+
+    #[test]
+    pub fn test_process_page_access_mru_page_not_in_memory_no_frame_available() {
+        let mut page_table: Vec<PTE> = vec![PTE::default(); 8];
+        let mut frame_pool: Vec<i32> = Vec::new();
+
+        page_table[2] = set_pte(true, 10, 3, 3, 1);
+        page_table[5] = set_pte(true, 20, 2, 4, 2);
+        page_table[7] = set_pte(true, 30, 1, 1, 1);
+
+        let frame = process_page_access_mru(&mut page_table, 0, &mut frame_pool, 12);
+
+        let mut expected_page_table: Vec<PTE> = vec![PTE::default(); 8];
+        expected_page_table[0] = set_pte(true, 20, 12, 12, 1);
+        expected_page_table[2] = set_pte(true, 10, 3, 3, 1);
+        expected_page_table[7] = set_pte(true, 30, 1, 1, 1);
+
+        assert_eq!(page_table, expected_page_table);
+        assert_eq!(frame, 20);
+    }
+
+    #[test]
+    pub fn test_count_page_faults_mru() {
+        let mut page_table: Vec<PTE> = vec![PTE::default(); 8];
+
+        let reference_string = vec![0, 3, 2, 6, 3, 4, 5, 2, 4, 5, 6];
+        let mut frame_pool: Vec<i32> = vec![0, 1, 2];
+
+        let faults = count_page_faults_mru(&mut page_table, reference_string, &mut frame_pool);
+
+        assert_eq!(faults, 9);
+    }
+
+    #[test]
+    pub fn test_count_page_faults_mru_cyclic_scan() {
+        let reference_string = vec![0, 1, 2, 3, 0, 1, 2, 3, 0, 1, 2, 3];
+
+        let mut page_table: Vec<PTE> = vec![PTE::default(); 8];
+        let mut frame_pool: Vec<i32> = vec![0, 1, 2];
+        let mru_faults =
+            count_page_faults_mru(&mut page_table, reference_string.clone(), &mut frame_pool);
+
+        let mut page_table: Vec<PTE> = vec![PTE::default(); 8];
+        let mut frame_pool: Vec<i32> = vec![0, 1, 2];
+        let lru_faults = count_page_faults_lru(&mut page_table, reference_string, &mut frame_pool);
+
+        assert_eq!(mru_faults, 6);
+        assert_eq!(lru_faults, 12);
+    }
+
+    #[test]
+    pub fn test_select_victim_mru_takes_first_of_tied_pages() {
+        let mut candidates: Vec<PTE> = vec![PTE::default(); 4];
+        candidates[0] = set_pte(true, 0, 1, 3, 1);
+        candidates[1] = set_pte(true, 1, 2, 5, 1);
+        candidates[3] = set_pte(true, 2, 4, 5, 1);
+
+        assert_eq!(select_victim(ReplacementPolicy::Mru, &candidates), Some(1));
+    }
+
+    #[test]
+    pub fn test_replacement_policy_count_page_faults() {
+        let reference_string = vec![0, 3, 2, 6, 3, 4, 5, 2, 4, 5, 6];
+        let expected = vec![
+            (ReplacementPolicy::Fifo, 8),
+            (ReplacementPolicy::Lru, 8),
+            (ReplacementPolicy::Lfu, 10),
+            (ReplacementPolicy::Mru, 9),
+            (ReplacementPolicy::Random(42), 10),
+            (ReplacementPolicy::Clock, 8),
+            (ReplacementPolicy::ClockPro, 10),
+            (ReplacementPolicy::Aging(AgingConfig::default()), 8),
+            (ReplacementPolicy::Arc, 10),
+            (ReplacementPolicy::Lirs, 10),
+            (ReplacementPolicy::TwoQ, 8),
+        ];
+
+        for (policy, faults) in expected {
+            let mut page_table: Vec<PTE> = vec![PTE::default(); 8];
+            let mut frame_pool: Vec<i32> = vec![0, 1, 2];
+
+            assert_eq!(
+                policy.count_page_faults(
+                    &mut page_table,
+                    reference_string.clone(),
+                    &mut frame_pool
+                ),
+                faults
+            );
+            assert_eq!(
+                policy.count_page_faults_for_frames(&reference_string, 3),
+                faults
+            );
+        }
+    }
+
+    #[test]
+    pub fn test_count_page_faults_opt() {
+        let mut page_table: Vec<PTE> = vec![PTE::default(); 8];
+        let mut frame_pool: Vec<i32> = vec![0, 1, 2];
+        let reference_string = vec![0, 3, 2, 6, 3, 4, 5, 2, 4, 5, 6];
+
+        let faults = process_references_opt(&mut page_table, &reference_string, &mut frame_pool);
+
+        assert_eq!(
+            faults,
+            vec![true, true, true, true, false, true, true, false, false, false, true]
+        );
+        assert!(page_table[6].is_valid);
+        assert!(!page_table[0].is_valid);
+
+        let mut page_table: Vec<PTE> = vec![PTE::default(); 8];
+        let mut frame_pool: Vec<i32> = vec![0, 1, 2];
+        let reference_string = vec![7, 0, 1, 2, 0, 3, 0, 4, 2, 3, 0, 3, 2, 1, 2, 0, 1, 7, 0, 1];
+
+        assert_eq!(
+            count_page_faults_opt(&mut page_table, reference_string, &mut frame_pool),
+            9
+        );
+    }
+
+    #[test]
+    pub fn test_opt_is_a_lower_bound() {
+        let reference_string = scan_reference_string();
+        let page_count = *reference_string.iter().max().unwrap() as usize + 1;
+
+        for frames in 1..6 {
+            let mut page_table: Vec<PTE> = vec![PTE::default(); page_count];
+            let mut frame_pool: Vec<i32> = (0..frames).collect();
+            let opt =
+                count_page_faults_opt(&mut page_table, reference_string.clone(), &mut frame_pool);
+            for policy in [
+                ReplacementPolicy::Fifo,
+                ReplacementPolicy::Lru,
+                ReplacementPolicy::Lfu,
+                ReplacementPolicy::Clock,
+                ReplacementPolicy::Arc,
+                ReplacementPolicy::Lirs,
+            ]
+            .iter()
+            {
+                assert!(
+                    policy.count_page_faults_for_frames(&reference_string, frames as usize) >= opt
+                );
+            }
+        }
+    }
+
+    #[test]
+    pub fn test_opt_faults_never_increase_with_frames() {
+        let reference_string = vec![1, 2, 3, 4, 1, 2, 5, 1, 2, 3, 4, 5];
+
+        let faults: Vec<i32> = (1..=6)
+            .map(|frames| {
+                let mut page_table: Vec<PTE> = vec![PTE::default(); 6];
+                let mut frame_pool: Vec<i32> = (0..frames).rev().collect();
+                count_page_faults_opt(&mut page_table, reference_string.clone(), &mut frame_pool)
+            })
+            .collect();
+
+        assert_eq!(faults, vec![12, 9, 7, 6, 5, 5]);
+    }
 }
@@ -0,0 +1,206 @@
+#[cfg(test)]
+
+mod test_workload {
+    use crate::r#virtual::r#virtual::{count_page_faults_lru, PTE};
+    use crate::r#virtual::workload::{generate_workload, Workload, WorkloadGenerator};
+    use std::collections::HashSet;
+
+    fn histogram(references: &[i32], page_count: usize) -> Vec<usize> {
+        let mut counts = vec![0; page_count];
+        for &page in references {
+            counts[page as usize] += 1;
+        }
+        counts
+    }
+
+    #[test]
+    pub fn test_generators_are_reproducible() {
+        let workload = Workload::Mixed {
+            components: vec![
+                (Workload::Uniform { page_count: 50 }, 1.0),
+                (
+                    Workload::Zipf {
+                        page_count: 50,
+                        exponent: 1.2,
+                    },
+                    2.0,
+                ),
+            ],
+        };
+
+        let first = generate_workload(&workload, 200, 9);
+
+        assert_eq!(first, generate_workload(&workload, 200, 9));
+        assert_ne!(first, generate_workload(&workload, 200, 10));
+        assert!(first.iter().all(|&page| (0..50).contains(&page)));
+    }
+
+    #[test]
+    pub fn test_uniform() {
+        let references = generate_workload(&Workload::Uniform { page_count: 10 }, 10000, 1);
+
+        let counts = histogram(&references, 10);
+        assert!(counts.iter().all(|&count| count > 850 && count < 1150));
+    }
+
+    #[test]
+    pub fn test_zipf() {
+        let references = generate_workload(
+            &Workload::Zipf {
+                page_count: 100,
+                exponent: 1.0,
+            },
+            20000,
+            2,
+        );
+
+        let counts = histogram(&references, 100);
+        let share = counts[0] as f64 / 20000.0;
+        assert!(share > 0.17 && share < 0.22);
+        assert!(counts[0] > counts[1]);
+        assert!(counts[1] > counts[10]);
+        assert!(counts[10] > counts[90]);
+    }
+
+    #[test]
+    pub fn test_scan_and_loop() {
+        assert_eq!(
+            generate_workload(&Workload::Scan { page_count: 4 }, 6, 0),
+            vec![0, 1, 2, 3, 0, 1]
+        );
+        let workload = Workload::Loop {
+            start: 5,
+            loop_length: 3,
+        };
+        assert_eq!(workload.page_count(), 8);
+        assert_eq!(
+            generate_workload(&workload, 7, 0),
+            vec![5, 6, 7, 5, 6, 7, 5]
+        );
+    }
+
+    #[test]
+    pub fn test_phase_change() {
+        let references = generate_workload(
+            &Workload::PhaseChange {
+                page_count: 1000,
+                working_set_size: 8,
+                phase_length: 100,
+                locality: 1.0,
+            },
+            500,
+            3,
+        );
+
+        let mut bases = HashSet::new();
+        for phase in references.chunks(100) {
+            let low = *phase.iter().min().unwrap();
+            let high = *phase.iter().max().unwrap();
+            assert!(high - low < 8);
+            bases.insert(low);
+        }
+        assert!(bases.len() > 1);
+    }
+
+    #[test]
+    pub fn test_mixed_weights() {
+        let workload = Workload::Mixed {
+            components: vec![
+                (
+                    Workload::Loop {
+                        start: 100,
+                        loop_length: 3,
+                    },
+                    1.0,
+                ),
+                (Workload::Uniform { page_count: 10 }, 0.0),
+            ],
+        };
+
+        let references: Vec<i32> = WorkloadGenerator::new(workload.clone(), 4)
+            .take(5)
+            .collect();
+
+        assert_eq!(references, vec![100, 101, 102, 100, 101]);
+        assert_eq!(workload.page_count(), 103);
+    }
+
+    fn rejects(workload: Workload) -> bool {
+        std::panic::catch_unwind(|| WorkloadGenerator::new(workload, 1)).is_err()
+    }
+
+    #[test]
+    pub fn test_invalid_workloads_rejected() {
+        let phase_change = |page_count, working_set_size, phase_length| Workload::PhaseChange {
+            page_count,
+            working_set_size,
+            phase_length,
+            locality: 0.9,
+        };
+        let invalid = vec![
+            Workload::Uniform { page_count: 0 },
+            Workload::Zipf {
+                page_count: 0,
+                exponent: 1.0,
+            },
+            Workload::Scan { page_count: 0 },
+            Workload::Loop {
+                start: 4,
+                loop_length: 0,
+            },
+            phase_change(0, 4, 10),
+            phase_change(16, 0, 10),
+            phase_change(16, 4, 0),
+            Workload::Mixed {
+                components: Vec::new(),
+            },
+            Workload::Mixed {
+                components: vec![
+                    (Workload::Scan { page_count: 4 }, 0.0),
+                    (Workload::Uniform { page_count: 4 }, 0.0),
+                ],
+            },
+            Workload::Mixed {
+                components: vec![(Workload::Scan { page_count: 4 }, -1.0)],
+            },
+            Workload::Mixed {
+                components: vec![(Workload::Scan { page_count: 0 }, 1.0)],
+            },
+        ];
+
+        for workload in invalid {
+            assert!(rejects(workload.clone()), "{:?}", workload);
+        }
+        assert!(!rejects(phase_change(16, 4, 10)));
+    }
+
+    #[test]
+    #[should_panic(expected = "mixed workload needs a positive weight")]
+    pub fn test_mixed_all_zero_weights_message() {
+        generate_workload(
+            &Workload::Mixed {
+                components: vec![(Workload::Scan { page_count: 4 }, 0.0)],
+            },
+            4,
+            1,
+        );
+    }
+
+    #[test]
+    pub fn test_loop_defeats_lru() {
+        let workload = Workload::Loop {
+            start: 0,
+            loop_length: 4,
+        };
+
+        for &(frames, expected) in [(3, 40), (4, 4)].iter() {
+            let mut page_table = vec![PTE::default(); workload.page_count()];
+            let mut frame_pool: Vec<i32> = (0..frames).collect();
+            let references = generate_workload(&workload, 40, 0);
+            assert_eq!(
+                count_page_faults_lru(&mut page_table, references, &mut frame_pool),
+                expected
+            );
+        }
+    }
+}
@@ -0,0 +1,271 @@
+use crate::r#virtual::r#virtual::{first_timestamp, SeededRng, PTE};
+use serde::{Deserialize, Serialize};
+
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TlbReplacement {
+    Lru,
+    Fifo,
+    Random(u64),
+}
+
+// `associativity` is the number of ways per set; use `entries` for a fully
+// associative TLB and 1 for a direct-mapped one. Without ASID tags every
+// context switch has to flush the TLB.
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TlbConfig {
+    pub entries: usize,
+    pub associativity: usize,
+    pub replacement: TlbReplacement,
+    pub asid_tagged: bool,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TlbEntry {
+    pub asid: u16,
+    pub page_number: u64,
+    pub frame_number: i32,
+    pub inserted_at: u64,
+    pub last_used: u64,
+}
+
+#[derive(Deserialize, Serialize, Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct TlbStats {
+    pub hits: usize,
+    pub misses: usize,
+    pub evictions: usize,
+    pub flushes: usize,
+}
+
+impl TlbStats {
+    pub fn hit_ratio(&self) -> f64 {
+        let lookups = self.hits + self.misses;
+        if lookups == 0 {
+            0.0
+        } else {
+            self.hits as f64 / lookups as f64
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Tlb {
+    pub config: TlbConfig,
+    pub sets: Vec<Vec<TlbEntry>>,
+    pub current_asid: u16,
+    pub stats: TlbStats,
+    clock: u64,
+    rng: SeededRng,
+}
+
+impl Tlb {
+    pub fn new(config: TlbConfig) -> Self {
+        let ways = config
+            .associativity
+            .clamp(1, std::cmp::max(config.entries, 1));
+        // `is_multiple_of` would need Rust 1.87.
+        #[allow(clippy::manual_is_multiple_of)]
+        let divides_evenly = config.entries % ways == 0;
+        assert!(
+            divides_evenly,
+            "TLB entries ({}) must be a multiple of the associativity ({})",
+            config.entries, ways
+        );
+        let set_count = std::cmp::max(config.entries / ways, 1);
+        let seed = match config.replacement {
+            TlbReplacement::Random(seed) => seed,
+            _ => 0,
+        };
+        Tlb {
+            config: TlbConfig {
+                associativity: ways,
+                ..config
+            },
+            sets: vec![Vec::new(); set_count],
+            current_asid: 0,
+            stats: TlbStats::default(),
+            clock: 0,
+            rng: SeededRng::new(seed),
+        }
+    }
+
+    fn set_index(&self, page_number: u64) -> usize {
+        (page_number % self.sets.len() as u64) as usize
+    }
+
+    pub fn lookup(&mut self, page_number: u64) -> Option<i32> {
+        self.clock += 1;
+        let set = self.set_index(page_number);
+        let asid = self.current_asid;
+        let clock = self.clock;
+        match self.sets[set]
+            .iter_mut()
+            .find(|e| e.asid == asid && e.page_number == page_number)
+        {
+            Some(entry) => {
+                entry.last_used = clock;
+                self.stats.hits += 1;
+                Some(entry.frame_number)
+            }
+            None => {
+                self.stats.misses += 1;
+                None
+            }
+        }
+    }
+
+    pub fn insert(&mut self, page_number: u64, frame_number: i32) {
+        if self.config.entries == 0 {
+            return;
+        }
+        self.clock += 1;
+        let set = self.set_index(page_number);
+        let entry = TlbEntry {
+            asid: self.current_asid,
+            page_number,
+            frame_number,
+            inserted_at: self.clock,
+            last_used: self.clock,
+        };
+        let asid = self.current_asid;
+        if let Some(existing) = self.sets[set]
+            .iter_mut()
+            .find(|e| e.asid == asid && e.page_number == page_number)
+        {
+            *existing = entry;
+            return;
+        }
+        if self.sets[set].len() < self.config.associativity {
+            self.sets[set].push(entry);
+            return;
+        }
+        let victim = match self.config.replacement {
+            TlbReplacement::Lru => (0..self.sets[set].len())
+                .min_by_key(|&i| self.sets[set][i].last_used)
+                .unwrap(),
+            TlbReplacement::Fifo => (0..self.sets[set].len())
+                .min_by_key(|&i| self.sets[set][i].inserted_at)
+                .unwrap(),
+            TlbReplacement::Random(_) => self.rng.next_below(self.sets[set].len()),
+        };
+        self.sets[set][victim] = entry;
+        self.stats.evictions += 1;
+    }
+
+    pub fn invalidate(&mut self, page_number: u64) {
+        let set = self.set_index(page_number);
+        let asid = self.current_asid;
+        self.sets[set].retain(|e| !(e.asid == asid && e.page_number == page_number));
+    }
+
+    pub fn invalidate_frame(&mut self, frame_number: i32) {
+        for set in self.sets.iter_mut() {
+            set.retain(|e| e.frame_number != frame_number);
+        }
+    }
+
+    pub fn flush(&mut self) {
+        for set in self.sets.iter_mut() {
+            set.clear();
+        }
+        self.stats.flushes += 1;
+    }
+
+    pub fn flush_asid(&mut self, asid: u16) {
+        for set in self.sets.iter_mut() {
+            set.retain(|e| e.asid != asid);
+        }
+        self.stats.flushes += 1;
+    }
+
+    pub fn context_switch(&mut self, asid: u16) {
+        if asid == self.current_asid {
+            return;
+        }
+        if !self.config.asid_tagged {
+            self.flush();
+        }
+        self.current_asid = asid;
+    }
+
+    pub fn occupancy(&self) -> usize {
+        self.sets.iter().map(|set| set.len()).sum()
+    }
+}
+
+// Latencies in nanoseconds. A TLB miss walks `page_table_levels` tables, each
+// costing one memory access, before the access itself.
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq)]
+pub struct AccessCosts {
+    pub tlb_lookup_ns: f64,
+    pub memory_access_ns: f64,
+    pub page_table_levels: usize,
+    pub page_fault_ns: f64,
+}
+
+pub fn effective_access_time(costs: &AccessCosts, tlb_hit_ratio: f64, page_fault_rate: f64) -> f64 {
+    let hit_time = costs.tlb_lookup_ns + costs.memory_access_ns;
+    let miss_time = costs.tlb_lookup_ns
+        + costs.page_table_levels as f64 * costs.memory_access_ns
+        + costs.memory_access_ns;
+    let no_fault_time = tlb_hit_ratio * hit_time + (1.0 - tlb_hit_ratio) * miss_time;
+    (1.0 - page_fault_rate) * no_fault_time + page_fault_rate * costs.page_fault_ns
+}
+
+#[derive(Deserialize, Serialize, Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct TranslationRun {
+    pub accesses: usize,
+    pub tlb_hits: usize,
+    pub tlb_misses: usize,
+    pub page_faults: usize,
+}
+
+impl TranslationRun {
+    pub fn effective_access_time(&self, costs: &AccessCosts) -> f64 {
+        if self.accesses == 0 {
+            return 0.0;
+        }
+        effective_access_time(
+            costs,
+            self.tlb_hits as f64 / self.accesses as f64,
+            self.page_faults as f64 / self.accesses as f64,
+        )
+    }
+}
+
+// Runs every reference through the TLB first; only misses consult the page
+// table. `process_page_access` is one of the `process_page_access_*` functions,
+// which still sees every access so its recency/frequency state stays exact.
+pub fn simulate_translation<F>(
+    tlb: &mut Tlb,
+    page_table: &mut Vec<PTE>,
+    page_references: Vec<i32>,
+    frame_pool: &mut Vec<i32>,
+    mut process_page_access: F,
+) -> TranslationRun
+where
+    F: FnMut(&mut Vec<PTE>, usize, &mut Vec<i32>, i32) -> i32,
+{
+    let mut run = TranslationRun::default();
+    let start_timestamp = first_timestamp(page_table);
+
+    for (current_timestamp, reference) in (start_timestamp..).zip(page_references) {
+        let page_number = reference as usize;
+        run.accesses += 1;
+        if tlb.lookup(page_number as u64).is_some() {
+            run.tlb_hits += 1;
+            process_page_access(page_table, page_number, frame_pool, current_timestamp);
+            continue;
+        }
+        run.tlb_misses += 1;
+        if !page_table[page_number].is_valid {
+            run.page_faults += 1;
+        }
+        let frame = process_page_access(page_table, page_number, frame_pool, current_timestamp);
+        if frame > -1 {
+            tlb.invalidate_frame(frame);
+            tlb.insert(page_number as u64, frame);
+        }
+    }
+
+    run
+}
@@ -0,0 +1,264 @@
+use crate::r#virtual::address::{AccessOutcome, AddressTranslator};
+use crate::r#virtual::cost::MemoryAccess;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::fs::File;
+use std::io::{self, BufRead, BufReader};
+use std::path::Path;
+
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TraceFormat {
+    // Whitespace separated decimal page numbers, any number per line. The
+    // page number is used as the address, so read these with a page size of
+    // one.
+    PlainPages,
+    // Dinero III `din`: `<label> <hex address>` per line, label 0 for a data
+    // read, 1 for a data write and 2 for an instruction fetch. The escape
+    // labels 3 and 4 are skipped; any other label is a parse error.
+    Dinero,
+    // Valgrind `--tool=lackey --trace-mem=yes`: `I`, ` L`, ` S` or ` M`
+    // followed by `<hex address>,<size>`. `==` lines are skipped.
+    Lackey,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AccessKind {
+    Read,
+    Write,
+    InstructionFetch,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TraceRecord {
+    pub address: u64,
+    pub kind: AccessKind,
+}
+
+impl TraceRecord {
+    pub fn page_number(&self, page_size: u64) -> u64 {
+        self.address / page_size
+    }
+
+    pub fn to_memory_access(&self, page_size: u64) -> MemoryAccess {
+        MemoryAccess {
+            page_number: self.page_number(page_size) as usize,
+            is_write: self.kind == AccessKind::Write,
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum TraceError {
+    Io(io::Error),
+    Parse { line: usize, message: String },
+}
+
+impl fmt::Display for TraceError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TraceError::Io(error) => write!(f, "trace read failed: {}", error),
+            TraceError::Parse { line, message } => write!(f, "line {}: {}", line, message),
+        }
+    }
+}
+
+impl std::error::Error for TraceError {}
+
+impl From<io::Error> for TraceError {
+    fn from(error: io::Error) -> Self {
+        TraceError::Io(error)
+    }
+}
+
+// Parses from any `BufRead` without holding the whole trace in memory: the
+// Dinero and Lackey formats one line at a time, plain pages one token at a
+// time, so even a trace written on a single line streams. Yields an error for
+// a malformed line (or plain token) and carries on with the next one.
+pub struct TraceReader<R: BufRead> {
+    reader: R,
+    format: TraceFormat,
+    line_number: usize,
+    line: String,
+    at_line_start: bool,
+}
+
+impl<R: BufRead> TraceReader<R> {
+    pub fn new(reader: R, format: TraceFormat) -> Self {
+        TraceReader {
+            reader,
+            format,
+            line_number: 0,
+            line: String::new(),
+            at_line_start: true,
+        }
+    }
+
+    pub fn line_number(&self) -> usize {
+        self.line_number
+    }
+
+    fn parse_error(&self, message: String) -> TraceError {
+        TraceError::Parse {
+            line: self.line_number,
+            message,
+        }
+    }
+
+    // At most one `0x` or `0X` prefix is accepted.
+    fn parse_hex(&self, text: &str) -> Result<u64, TraceError> {
+        let digits = text
+            .strip_prefix("0x")
+            .or_else(|| text.strip_prefix("0X"))
+            .unwrap_or(text);
+        u64::from_str_radix(digits, 16)
+            .map_err(|_| self.parse_error(format!("bad address `{}`", text)))
+    }
+
+    // Reads the next whitespace-separated token straight from the reader's
+    // buffer, counting lines as it goes. Returns None at the end of input.
+    fn next_token(&mut self) -> io::Result<Option<Vec<u8>>> {
+        let mut token = Vec::new();
+        loop {
+            let buffer = self.reader.fill_buf()?;
+            if buffer.is_empty() {
+                return Ok(if token.is_empty() { None } else { Some(token) });
+            }
+            let mut used = 0;
+            let mut done = false;
+            for &byte in buffer {
+                if byte.is_ascii_whitespace() && !token.is_empty() {
+                    done = true;
+                    break;
+                }
+                used += 1;
+                if self.at_line_start {
+                    self.line_number += 1;
+                    self.at_line_start = false;
+                }
+                if byte == b'\n' {
+                    self.at_line_start = true;
+                } else if !byte.is_ascii_whitespace() {
+                    token.push(byte);
+                }
+            }
+            self.reader.consume(used);
+            if done {
+                return Ok(Some(token));
+            }
+        }
+    }
+
+    fn next_plain(&mut self) -> Option<Result<TraceRecord, TraceError>> {
+        let token = match self.next_token() {
+            Ok(token) => token?,
+            Err(error) => return Some(Err(TraceError::Io(error))),
+        };
+        let text = String::from_utf8_lossy(&token);
+        Some(
+            text.parse::<u64>()
+                .map(|address| TraceRecord {
+                    address,
+                    kind: AccessKind::Read,
+                })
+                .map_err(|_| self.parse_error(format!("bad page number `{}`", text))),
+        )
+    }
+
+    fn parse_dinero(&self) -> Result<Option<TraceRecord>, TraceError> {
+        let mut fields = self.line.split_whitespace();
+        let label = match fields.next() {
+            Some(label) => label,
+            None => return Ok(None),
+        };
+        let kind = match label {
+            "0" => AccessKind::Read,
+            "1" => AccessKind::Write,
+            "2" => AccessKind::InstructionFetch,
+            "3" | "4" => return Ok(None),
+            _ => return Err(self.parse_error(format!("bad label `{}`", label))),
+        };
+        let address = match fields.next() {
+            Some(address) => self.parse_hex(address)?,
+            None => return Err(self.parse_error("missing address".to_string())),
+        };
+        Ok(Some(TraceRecord { address, kind }))
+    }
+
+    fn parse_lackey(&self) -> Result<Option<TraceRecord>, TraceError> {
+        let line = self.line.trim();
+        if line.is_empty() || line.starts_with("==") {
+            return Ok(None);
+        }
+        let mut fields = line.split_whitespace();
+        let kind = match fields.next() {
+            Some("I") => AccessKind::InstructionFetch,
+            Some("L") => AccessKind::Read,
+            Some("S") | Some("M") => AccessKind::Write,
+            Some(other) => return Err(self.parse_error(format!("bad access type `{}`", other))),
+            None => return Ok(None),
+        };
+        let address = match fields.next().and_then(|field| field.split(',').next()) {
+            Some(address) => self.parse_hex(address)?,
+            None => return Err(self.parse_error("missing address".to_string())),
+        };
+        Ok(Some(TraceRecord { address, kind }))
+    }
+}
+
+impl<R: BufRead> Iterator for TraceReader<R> {
+    type Item = Result<TraceRecord, TraceError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.format == TraceFormat::PlainPages {
+            return self.next_plain();
+        }
+        loop {
+            self.line.clear();
+            match self.reader.read_line(&mut self.line) {
+                Ok(0) => return None,
+                Ok(_) => {}
+                Err(error) => return Some(Err(TraceError::Io(error))),
+            }
+            self.line_number += 1;
+            let parsed = match self.format {
+                TraceFormat::Dinero => self.parse_dinero(),
+                _ => self.parse_lackey(),
+            };
+            match parsed {
+                Ok(Some(record)) => return Some(Ok(record)),
+                Ok(None) => {}
+                Err(error) => return Some(Err(error)),
+            }
+        }
+    }
+}
+
+pub fn open_trace(path: &Path, format: TraceFormat) -> io::Result<TraceReader<BufReader<File>>> {
+    Ok(TraceReader::new(BufReader::new(File::open(path)?), format))
+}
+
+#[derive(Deserialize, Serialize, Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct TraceRun {
+    pub records: usize,
+    pub hits: usize,
+    pub faults: usize,
+    pub out_of_range: usize,
+}
+
+// Feeds a trace straight into a translator, stopping at the first error.
+pub fn run_trace<I>(translator: &mut AddressTranslator, records: I) -> Result<TraceRun, TraceError>
+where
+    I: IntoIterator<Item = Result<TraceRecord, TraceError>>,
+{
+    let mut run = TraceRun::default();
+    for record in records {
+        let record = record?;
+        run.records += 1;
+        match translator.access(record.address) {
+            AccessOutcome::Hit { .. } => run.hits += 1,
+            AccessOutcome::Fault { .. } | AccessOutcome::NoFrame { .. } => run.faults += 1,
+            AccessOutcome::OutOfRange { .. } => run.out_of_range += 1,
+        }
+    }
+    Ok(run)
+}
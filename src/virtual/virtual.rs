@@ -1,12 +1,17 @@
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeSet, HashMap, VecDeque};
 use std::usize;
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq, Eq)]
 pub struct PTE {
     pub is_valid: bool,
     pub frame_number: i32,
     pub arrival_timestamp: i32,
     pub last_access_timestamp: i32,
     pub reference_count: i32,
+    pub is_read_only: bool,
+    pub is_cow: bool,
+    pub is_dirty: bool,
 }
 
 impl Default for PTE {
@@ -17,6 +22,9 @@ impl Default for PTE {
             arrival_timestamp: -1,
             last_access_timestamp: -1,
             reference_count: -1,
+            is_read_only: false,
+            is_cow: false,
+            is_dirty: false,
         }
     }
 }
@@ -27,23 +35,44 @@ fn allocate_memory(
     frame: i32,
     current_timestamp: i32,
 ) {
+    load_page(page_table, page_number, frame, current_timestamp);
+}
+
+fn replace_memory(
+    position: i32,
+    page_table: &mut Vec<PTE>,
+    page_number: usize,
+    frame: i32,
+    current_timestamp: i32,
+) {
+    let p = position as usize;
+    page_table[p] = PTE::default();
+    allocate_memory(page_table, page_number, frame, current_timestamp);
+}
+
+// Slice versions of the two helpers above, for the policies that take the page
+// table as `&mut [PTE]`.
+fn load_page(page_table: &mut [PTE], page_number: usize, frame: i32, current_timestamp: i32) {
     page_table[page_number].arrival_timestamp = current_timestamp;
     page_table[page_number].frame_number = frame;
     page_table[page_number].is_valid = true;
     page_table[page_number].last_access_timestamp = current_timestamp;
     page_table[page_number].reference_count = 1;
+    page_table[page_number].is_read_only = false;
+    page_table[page_number].is_cow = false;
+    page_table[page_number].is_dirty = false;
 }
 
-fn replace_memory(
+fn replace_page(
     position: i32,
-    page_table: &mut Vec<PTE>,
+    page_table: &mut [PTE],
     page_number: usize,
     frame: i32,
     current_timestamp: i32,
 ) {
     let p = position as usize;
     page_table[p] = PTE::default();
-    allocate_memory(page_table, page_number, frame, current_timestamp);
+    load_page(page_table, page_number, frame, current_timestamp);
 }
 
 pub fn process_page_access_fifo(
@@ -360,3 +389,1436 @@ pub fn count_page_faults_lfu(
 
     return faults;
 }
+
+pub(crate) fn first_timestamp(page_table: &[PTE]) -> i32 {
+    let mut current_timestamp = 0;
+    for pte in page_table.iter() {
+        if pte.arrival_timestamp > current_timestamp {
+            current_timestamp = pte.arrival_timestamp;
+        }
+    }
+    current_timestamp + 1
+}
+
+fn resident_pages_by_last_access(page_table: &[PTE]) -> Vec<usize> {
+    let mut resident: Vec<usize> = (0..page_table.len())
+        .filter(|&i| page_table[i].is_valid)
+        .collect();
+    resident.sort_by_key(|&i| page_table[i].last_access_timestamp);
+    resident
+}
+
+fn evict_page(page_table: &mut [PTE], page_number: usize) -> i32 {
+    let frame = page_table[page_number].frame_number;
+    page_table[page_number] = PTE::default();
+    frame
+}
+
+fn remove_page(list: &mut VecDeque<usize>, page_number: usize) -> bool {
+    match list.iter().position(|&p| p == page_number) {
+        Some(i) => {
+            list.remove(i);
+            true
+        }
+        None => false,
+    }
+}
+
+// Lists are ordered from LRU (front) to MRU (back). T1/T2 hold resident pages,
+// B1/B2 are ghost lists remembering pages recently evicted from T1/T2.
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
+pub struct ArcState {
+    pub capacity: usize,
+    pub p: usize,
+    pub t1: VecDeque<usize>,
+    pub t2: VecDeque<usize>,
+    pub b1: VecDeque<usize>,
+    pub b2: VecDeque<usize>,
+    pub p_history: Vec<usize>,
+}
+
+impl ArcState {
+    pub fn new(page_table: &[PTE], frame_pool: &[i32]) -> Self {
+        let resident = resident_pages_by_last_access(page_table);
+        ArcState {
+            capacity: resident.len() + frame_pool.len(),
+            p: 0,
+            t1: resident.into_iter().collect(),
+            t2: VecDeque::new(),
+            b1: VecDeque::new(),
+            b2: VecDeque::new(),
+            p_history: Vec::new(),
+        }
+    }
+
+    fn replace(&mut self, page_table: &mut [PTE], in_b2: bool) -> i32 {
+        let t1_len = self.t1.len();
+        if t1_len > 0 && ((in_b2 && t1_len == self.p) || t1_len > self.p || self.t2.is_empty()) {
+            let victim = self.t1.pop_front().unwrap();
+            self.b1.push_back(victim);
+            evict_page(page_table, victim)
+        } else {
+            let victim = self.t2.pop_front().unwrap();
+            self.b2.push_back(victim);
+            evict_page(page_table, victim)
+        }
+    }
+
+    fn take_frame(
+        &mut self,
+        page_table: &mut [PTE],
+        frame_pool: &mut Vec<i32>,
+        in_b2: bool,
+    ) -> i32 {
+        match frame_pool.pop() {
+            Some(frame) => frame,
+            None => self.replace(page_table, in_b2),
+        }
+    }
+}
+
+pub fn process_page_access_arc(
+    arc_state: &mut ArcState,
+    page_table: &mut [PTE],
+    page_number: usize,
+    frame_pool: &mut Vec<i32>,
+    current_timestamp: i32,
+) -> i32 {
+    if arc_state.capacity == 0 {
+        return -1;
+    }
+
+    let frame: i32;
+    if page_table[page_number].is_valid {
+        page_table[page_number].last_access_timestamp = current_timestamp;
+        page_table[page_number].reference_count += 1;
+        frame = page_table[page_number].frame_number;
+        if !remove_page(&mut arc_state.t1, page_number) {
+            remove_page(&mut arc_state.t2, page_number);
+        }
+        arc_state.t2.push_back(page_number);
+    } else if remove_page(&mut arc_state.b1, page_number) {
+        let delta = std::cmp::max(arc_state.b2.len() / (arc_state.b1.len() + 1), 1);
+        arc_state.p = std::cmp::min(arc_state.p + delta, arc_state.capacity);
+        frame = arc_state.take_frame(page_table, frame_pool, false);
+        load_page(page_table, page_number, frame, current_timestamp);
+        arc_state.t2.push_back(page_number);
+    } else if remove_page(&mut arc_state.b2, page_number) {
+        let delta = std::cmp::max(arc_state.b1.len() / (arc_state.b2.len() + 1), 1);
+        arc_state.p = arc_state.p.saturating_sub(delta);
+        frame = arc_state.take_frame(page_table, frame_pool, true);
+        load_page(page_table, page_number, frame, current_timestamp);
+        arc_state.t2.push_back(page_number);
+    } else {
+        let l1 = arc_state.t1.len() + arc_state.b1.len();
+        let total = l1 + arc_state.t2.len() + arc_state.b2.len();
+        if l1 >= arc_state.capacity {
+            if arc_state.t1.len() < arc_state.capacity {
+                arc_state.b1.pop_front();
+                frame = arc_state.take_frame(page_table, frame_pool, false);
+            } else {
+                let victim = arc_state.t1.pop_front().unwrap();
+                frame = evict_page(page_table, victim);
+            }
+        } else {
+            if total >= 2 * arc_state.capacity {
+                arc_state.b2.pop_front();
+            }
+            frame = arc_state.take_frame(page_table, frame_pool, false);
+        }
+        load_page(page_table, page_number, frame, current_timestamp);
+        arc_state.t1.push_back(page_number);
+    }
+
+    arc_state.p_history.push(arc_state.p);
+    frame
+}
+
+pub fn count_page_faults_arc_with_state(
+    arc_state: &mut ArcState,
+    page_table: &mut [PTE],
+    page_references: Vec<i32>,
+    frame_pool: &mut Vec<i32>,
+) -> i32 {
+    let mut faults: i32 = 0;
+    let start_timestamp = first_timestamp(page_table);
+
+    for (current_timestamp, reference) in (start_timestamp..).zip(page_references) {
+        let page_number = reference as usize;
+        if !page_table[page_number].is_valid {
+            faults += 1;
+        }
+        process_page_access_arc(
+            arc_state,
+            page_table,
+            page_number,
+            frame_pool,
+            current_timestamp,
+        );
+    }
+
+    faults
+}
+
+pub fn count_page_faults_arc(
+    page_table: &mut [PTE],
+    page_references: Vec<i32>,
+    frame_pool: &mut Vec<i32>,
+) -> i32 {
+    let mut arc_state = ArcState::new(page_table, frame_pool);
+    count_page_faults_arc_with_state(&mut arc_state, page_table, page_references, frame_pool)
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LirsStatus {
+    Lir,
+    ResidentHir,
+    NonResidentHir,
+}
+
+// The stack is ordered from bottom (front) to top (back) and the queue of
+// resident HIR pages from next victim (front) to most recent (back).
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
+pub struct LirsState {
+    pub lir_capacity: usize,
+    pub lir_count: usize,
+    pub stack: VecDeque<usize>,
+    pub queue: VecDeque<usize>,
+    pub status: HashMap<usize, LirsStatus>,
+}
+
+impl LirsState {
+    pub fn new(page_table: &[PTE], frame_pool: &[i32]) -> Self {
+        let capacity = page_table.iter().filter(|pte| pte.is_valid).count() + frame_pool.len();
+        let hir_capacity = std::cmp::max(capacity / 100, 1);
+        LirsState::with_hir_capacity(page_table, frame_pool, hir_capacity)
+    }
+
+    pub fn with_hir_capacity(page_table: &[PTE], frame_pool: &[i32], hir_capacity: usize) -> Self {
+        let resident = resident_pages_by_last_access(page_table);
+        let capacity = resident.len() + frame_pool.len();
+        let mut lirs_state = LirsState {
+            lir_capacity: capacity.saturating_sub(hir_capacity),
+            lir_count: 0,
+            stack: VecDeque::new(),
+            queue: VecDeque::new(),
+            status: HashMap::new(),
+        };
+        for page_number in resident {
+            lirs_state.stack.push_back(page_number);
+            if lirs_state.lir_count < lirs_state.lir_capacity {
+                lirs_state.status.insert(page_number, LirsStatus::Lir);
+                lirs_state.lir_count += 1;
+            } else {
+                lirs_state
+                    .status
+                    .insert(page_number, LirsStatus::ResidentHir);
+                lirs_state.queue.push_back(page_number);
+            }
+        }
+        lirs_state.prune_stack();
+        lirs_state
+    }
+
+    fn move_to_top(&mut self, page_number: usize) {
+        remove_page(&mut self.stack, page_number);
+        self.stack.push_back(page_number);
+    }
+
+    fn prune_stack(&mut self) {
+        while let Some(&bottom) = self.stack.front() {
+            match self.status.get(&bottom) {
+                Some(LirsStatus::Lir) => break,
+                Some(LirsStatus::NonResidentHir) => {
+                    self.status.remove(&bottom);
+                }
+                _ => {}
+            }
+            self.stack.pop_front();
+        }
+    }
+
+    fn demote_bottom_lir(&mut self) {
+        self.prune_stack();
+        if let Some(bottom) = self.stack.pop_front() {
+            self.status.insert(bottom, LirsStatus::ResidentHir);
+            self.queue.push_back(bottom);
+            self.lir_count -= 1;
+        }
+        self.prune_stack();
+    }
+
+    fn take_frame(&mut self, page_table: &mut [PTE], frame_pool: &mut Vec<i32>) -> i32 {
+        if let Some(frame) = frame_pool.pop() {
+            return frame;
+        }
+        let victim = match self.queue.pop_front() {
+            Some(victim) => victim,
+            None => {
+                self.prune_stack();
+                self.lir_count -= 1;
+                self.stack.pop_front().unwrap()
+            }
+        };
+        if self.stack.contains(&victim) {
+            self.status.insert(victim, LirsStatus::NonResidentHir);
+        } else {
+            self.status.remove(&victim);
+        }
+        evict_page(page_table, victim)
+    }
+}
+
+pub fn process_page_access_lirs(
+    lirs_state: &mut LirsState,
+    page_table: &mut [PTE],
+    page_number: usize,
+    frame_pool: &mut Vec<i32>,
+    current_timestamp: i32,
+) -> i32 {
+    if page_table[page_number].is_valid {
+        page_table[page_number].last_access_timestamp = current_timestamp;
+        page_table[page_number].reference_count += 1;
+        if lirs_state.status.get(&page_number) == Some(&LirsStatus::Lir) {
+            lirs_state.move_to_top(page_number);
+            lirs_state.prune_stack();
+        } else if lirs_state.stack.contains(&page_number) {
+            lirs_state.move_to_top(page_number);
+            remove_page(&mut lirs_state.queue, page_number);
+            lirs_state.status.insert(page_number, LirsStatus::Lir);
+            lirs_state.lir_count += 1;
+            lirs_state.demote_bottom_lir();
+        } else {
+            lirs_state.stack.push_back(page_number);
+            remove_page(&mut lirs_state.queue, page_number);
+            lirs_state.queue.push_back(page_number);
+        }
+        return page_table[page_number].frame_number;
+    }
+
+    if frame_pool.is_empty() && page_table.iter().all(|pte| !pte.is_valid) {
+        return -1;
+    }
+
+    let frame = lirs_state.take_frame(page_table, frame_pool);
+    load_page(page_table, page_number, frame, current_timestamp);
+
+    if lirs_state.lir_count < lirs_state.lir_capacity {
+        lirs_state.move_to_top(page_number);
+        lirs_state.status.insert(page_number, LirsStatus::Lir);
+        lirs_state.lir_count += 1;
+    } else if lirs_state.status.get(&page_number) == Some(&LirsStatus::NonResidentHir) {
+        lirs_state.move_to_top(page_number);
+        lirs_state.status.insert(page_number, LirsStatus::Lir);
+        lirs_state.lir_count += 1;
+        lirs_state.demote_bottom_lir();
+    } else {
+        lirs_state.stack.push_back(page_number);
+        lirs_state
+            .status
+            .insert(page_number, LirsStatus::ResidentHir);
+        lirs_state.queue.push_back(page_number);
+    }
+
+    frame
+}
+
+pub fn count_page_faults_lirs(
+    page_table: &mut [PTE],
+    page_references: Vec<i32>,
+    frame_pool: &mut Vec<i32>,
+) -> i32 {
+    let mut lirs_state = LirsState::new(page_table, frame_pool);
+    let mut faults: i32 = 0;
+    let start_timestamp = first_timestamp(page_table);
+
+    for (current_timestamp, reference) in (start_timestamp..).zip(page_references) {
+        let page_number = reference as usize;
+        if !page_table[page_number].is_valid {
+            faults += 1;
+        }
+        process_page_access_lirs(
+            &mut lirs_state,
+            page_table,
+            page_number,
+            frame_pool,
+            current_timestamp,
+        );
+    }
+
+    faults
+}
+
+// A1in is a FIFO of pages seen once, A1out remembers pages recently evicted
+// from A1in, and Am is an LRU list (front is least recent) of pages seen again.
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
+pub struct TwoQState {
+    pub kin: usize,
+    pub kout: usize,
+    pub a1in: VecDeque<usize>,
+    pub a1out: VecDeque<usize>,
+    pub am: VecDeque<usize>,
+}
+
+impl TwoQState {
+    pub fn new(page_table: &[PTE], frame_pool: &[i32]) -> Self {
+        let capacity = page_table.iter().filter(|pte| pte.is_valid).count() + frame_pool.len();
+        TwoQState::with_sizes(
+            page_table,
+            std::cmp::max(capacity / 4, 1),
+            std::cmp::max(capacity / 2, 1),
+        )
+    }
+
+    pub fn with_sizes(page_table: &[PTE], kin: usize, kout: usize) -> Self {
+        let mut resident: Vec<usize> = (0..page_table.len())
+            .filter(|&i| page_table[i].is_valid)
+            .collect();
+        resident.sort_by_key(|&i| page_table[i].arrival_timestamp);
+        TwoQState {
+            kin,
+            kout,
+            a1in: resident.into_iter().collect(),
+            a1out: VecDeque::new(),
+            am: VecDeque::new(),
+        }
+    }
+
+    fn take_frame(&mut self, page_table: &mut [PTE], frame_pool: &mut Vec<i32>) -> i32 {
+        if let Some(frame) = frame_pool.pop() {
+            return frame;
+        }
+        if self.a1in.len() > self.kin || self.am.is_empty() {
+            let victim = self.a1in.pop_front().unwrap();
+            self.a1out.push_back(victim);
+            if self.a1out.len() > self.kout {
+                self.a1out.pop_front();
+            }
+            evict_page(page_table, victim)
+        } else {
+            let victim = self.am.pop_front().unwrap();
+            evict_page(page_table, victim)
+        }
+    }
+}
+
+pub fn process_page_access_2q(
+    two_q_state: &mut TwoQState,
+    page_table: &mut [PTE],
+    page_number: usize,
+    frame_pool: &mut Vec<i32>,
+    current_timestamp: i32,
+) -> i32 {
+    if page_table[page_number].is_valid {
+        page_table[page_number].last_access_timestamp = current_timestamp;
+        page_table[page_number].reference_count += 1;
+        if remove_page(&mut two_q_state.am, page_number) {
+            two_q_state.am.push_back(page_number);
+        }
+        return page_table[page_number].frame_number;
+    }
+
+    if frame_pool.is_empty() && two_q_state.a1in.is_empty() && two_q_state.am.is_empty() {
+        return -1;
+    }
+
+    let seen_before = remove_page(&mut two_q_state.a1out, page_number);
+    let frame = two_q_state.take_frame(page_table, frame_pool);
+    load_page(page_table, page_number, frame, current_timestamp);
+    if seen_before {
+        two_q_state.am.push_back(page_number);
+    } else {
+        two_q_state.a1in.push_back(page_number);
+    }
+
+    frame
+}
+
+pub fn count_page_faults_2q(
+    page_table: &mut [PTE],
+    page_references: Vec<i32>,
+    frame_pool: &mut Vec<i32>,
+) -> i32 {
+    let mut two_q_state = TwoQState::new(page_table, frame_pool);
+    let mut faults: i32 = 0;
+    let start_timestamp = first_timestamp(page_table);
+
+    for (current_timestamp, reference) in (start_timestamp..).zip(page_references) {
+        let page_number = reference as usize;
+        if !page_table[page_number].is_valid {
+            faults += 1;
+        }
+        process_page_access_2q(
+            &mut two_q_state,
+            page_table,
+            page_number,
+            frame_pool,
+            current_timestamp,
+        );
+    }
+
+    faults
+}
+
+// Resident pages in the order the clock hand visits them; `referenced` holds the
+// reference bit for the page in the same slot of `ring`.
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
+pub struct ClockState {
+    pub ring: Vec<usize>,
+    pub referenced: Vec<bool>,
+    pub hand: usize,
+}
+
+impl ClockState {
+    pub fn new(page_table: &[PTE]) -> Self {
+        let mut resident: Vec<usize> = (0..page_table.len())
+            .filter(|&i| page_table[i].is_valid)
+            .collect();
+        resident.sort_by_key(|&i| page_table[i].arrival_timestamp);
+        let referenced = vec![false; resident.len()];
+        ClockState {
+            ring: resident,
+            referenced,
+            hand: 0,
+        }
+    }
+}
+
+pub fn process_page_access_clock(
+    clock_state: &mut ClockState,
+    page_table: &mut [PTE],
+    page_number: usize,
+    frame_pool: &mut Vec<i32>,
+    current_timestamp: i32,
+) -> i32 {
+    if page_table[page_number].is_valid {
+        page_table[page_number].last_access_timestamp = current_timestamp;
+        page_table[page_number].reference_count += 1;
+        if let Some(slot) = clock_state.ring.iter().position(|&p| p == page_number) {
+            clock_state.referenced[slot] = true;
+        }
+        return page_table[page_number].frame_number;
+    }
+
+    if let Some(frame) = frame_pool.pop() {
+        load_page(page_table, page_number, frame, current_timestamp);
+        clock_state.ring.push(page_number);
+        clock_state.referenced.push(true);
+        return frame;
+    }
+
+    if clock_state.ring.is_empty() {
+        return -1;
+    }
+
+    while clock_state.referenced[clock_state.hand] {
+        clock_state.referenced[clock_state.hand] = false;
+        clock_state.hand = (clock_state.hand + 1) % clock_state.ring.len();
+    }
+
+    let victim = clock_state.ring[clock_state.hand];
+    let frame = evict_page(page_table, victim);
+    load_page(page_table, page_number, frame, current_timestamp);
+    clock_state.ring[clock_state.hand] = page_number;
+    clock_state.referenced[clock_state.hand] = true;
+    clock_state.hand = (clock_state.hand + 1) % clock_state.ring.len();
+
+    frame
+}
+
+pub fn count_page_faults_clock(
+    page_table: &mut [PTE],
+    page_references: Vec<i32>,
+    frame_pool: &mut Vec<i32>,
+) -> i32 {
+    let mut clock_state = ClockState::new(page_table);
+    let mut faults: i32 = 0;
+    let start_timestamp = first_timestamp(page_table);
+
+    for (current_timestamp, reference) in (start_timestamp..).zip(page_references) {
+        let page_number = reference as usize;
+        if !page_table[page_number].is_valid {
+            faults += 1;
+        }
+        process_page_access_clock(
+            &mut clock_state,
+            page_table,
+            page_number,
+            frame_pool,
+            current_timestamp,
+        );
+    }
+
+    faults
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ClockProEntry {
+    pub page_number: usize,
+    pub is_hot: bool,
+    pub is_resident: bool,
+    pub is_referenced: bool,
+    pub in_test: bool,
+}
+
+// All pages, hot, cold and non-resident cold pages still in their test period,
+// share one clock. HAND_hot demotes hot pages, HAND_cold evicts cold pages and
+// HAND_test ends test periods. `cold_target` is the adaptive m_c of the paper.
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
+pub struct ClockProState {
+    pub capacity: usize,
+    pub cold_target: usize,
+    pub entries: Vec<ClockProEntry>,
+    pub hand_hot: usize,
+    pub hand_cold: usize,
+    pub hand_test: usize,
+    pub hot_count: usize,
+    pub cold_count: usize,
+    pub non_resident_count: usize,
+}
+
+impl ClockProState {
+    pub fn new(page_table: &[PTE], frame_pool: &[i32]) -> Self {
+        let mut resident: Vec<usize> = (0..page_table.len())
+            .filter(|&i| page_table[i].is_valid)
+            .collect();
+        resident.sort_by_key(|&i| page_table[i].arrival_timestamp);
+        let mut clock_pro_state = ClockProState {
+            capacity: resident.len() + frame_pool.len(),
+            cold_target: 1,
+            entries: Vec::new(),
+            hand_hot: 0,
+            hand_cold: 0,
+            hand_test: 0,
+            hot_count: 0,
+            cold_count: 0,
+            non_resident_count: 0,
+        };
+        for page_number in resident {
+            clock_pro_state.insert_at_head(ClockProEntry {
+                page_number,
+                is_hot: false,
+                is_resident: true,
+                is_referenced: false,
+                in_test: false,
+            });
+            clock_pro_state.cold_count += 1;
+        }
+        clock_pro_state
+    }
+
+    fn insert_at_head(&mut self, entry: ClockProEntry) {
+        let index = self.hand_hot;
+        self.entries.insert(index, entry);
+        let len = self.entries.len();
+        for hand in [&mut self.hand_hot, &mut self.hand_cold, &mut self.hand_test] {
+            if *hand >= index && len > 1 {
+                *hand += 1;
+            }
+            *hand %= len;
+        }
+    }
+
+    fn remove_entry(&mut self, index: usize) {
+        self.entries.remove(index);
+        let len = self.entries.len();
+        for hand in [&mut self.hand_hot, &mut self.hand_cold, &mut self.hand_test] {
+            if *hand > index {
+                *hand -= 1;
+            }
+            if *hand >= len {
+                *hand = 0;
+            }
+        }
+    }
+
+    fn advance(&self, hand: usize) -> usize {
+        (hand + 1) % self.entries.len()
+    }
+
+    fn end_test_period(&mut self) {
+        self.cold_target = std::cmp::max(self.cold_target - 1, 1);
+    }
+
+    fn run_hand_hot(&mut self) {
+        while self.hot_count > 0 {
+            let index = self.hand_hot;
+            let entry = self.entries[index];
+            if entry.is_hot {
+                self.hand_hot = self.advance(index);
+                if entry.is_referenced {
+                    self.entries[index].is_referenced = false;
+                } else {
+                    self.entries[index].is_hot = false;
+                    self.hot_count -= 1;
+                    self.cold_count += 1;
+                    return;
+                }
+            } else if !entry.is_resident {
+                self.remove_entry(index);
+                self.non_resident_count -= 1;
+                self.end_test_period();
+            } else {
+                if entry.in_test {
+                    self.entries[index].in_test = false;
+                    self.end_test_period();
+                }
+                self.hand_hot = self.advance(index);
+            }
+        }
+    }
+
+    fn run_hand_test(&mut self) {
+        while self.non_resident_count > 0 {
+            let index = self.hand_test;
+            let entry = self.entries[index];
+            if !entry.is_resident {
+                self.remove_entry(index);
+                self.non_resident_count -= 1;
+                self.end_test_period();
+                return;
+            }
+            if !entry.is_hot && entry.in_test {
+                self.entries[index].in_test = false;
+                self.end_test_period();
+            }
+            self.hand_test = self.advance(index);
+        }
+    }
+
+    fn balance_hot_pages(&mut self) {
+        while self.hot_count > 0 && self.hot_count + self.cold_target > self.capacity {
+            self.run_hand_hot();
+        }
+    }
+
+    fn run_hand_cold(&mut self, page_table: &mut [PTE]) -> i32 {
+        if self.cold_count == 0 {
+            self.run_hand_hot();
+        }
+        loop {
+            let index = self.hand_cold;
+            let entry = self.entries[index];
+            if entry.is_hot || !entry.is_resident {
+                self.hand_cold = self.advance(index);
+            } else if entry.is_referenced {
+                self.entries[index].is_referenced = false;
+                if entry.in_test {
+                    self.entries[index].is_hot = true;
+                    self.entries[index].in_test = false;
+                    self.hot_count += 1;
+                    self.cold_count -= 1;
+                    self.cold_target = std::cmp::min(self.cold_target + 1, self.capacity);
+                    self.hand_cold = self.advance(index);
+                    self.balance_hot_pages();
+                    if self.cold_count == 0 {
+                        self.run_hand_hot();
+                    }
+                } else {
+                    self.entries[index].in_test = true;
+                    self.hand_cold = self.advance(index);
+                }
+            } else {
+                let frame = evict_page(page_table, entry.page_number);
+                self.cold_count -= 1;
+                if entry.in_test {
+                    self.entries[index].is_resident = false;
+                    self.non_resident_count += 1;
+                    self.hand_cold = self.advance(index);
+                    while self.non_resident_count > self.capacity {
+                        self.run_hand_test();
+                    }
+                } else {
+                    self.remove_entry(index);
+                }
+                return frame;
+            }
+        }
+    }
+}
+
+pub fn process_page_access_clock_pro(
+    clock_pro_state: &mut ClockProState,
+    page_table: &mut [PTE],
+    page_number: usize,
+    frame_pool: &mut Vec<i32>,
+    current_timestamp: i32,
+) -> i32 {
+    let position = clock_pro_state
+        .entries
+        .iter()
+        .position(|e| e.page_number == page_number);
+
+    if page_table[page_number].is_valid {
+        page_table[page_number].last_access_timestamp = current_timestamp;
+        page_table[page_number].reference_count += 1;
+        if let Some(index) = position {
+            clock_pro_state.entries[index].is_referenced = true;
+        }
+        return page_table[page_number].frame_number;
+    }
+
+    if clock_pro_state.capacity == 0 {
+        return -1;
+    }
+
+    let mut in_test = false;
+    if let Some(index) = position {
+        in_test = true;
+        clock_pro_state.remove_entry(index);
+        clock_pro_state.non_resident_count -= 1;
+    }
+
+    let warming_up = !frame_pool.is_empty()
+        && clock_pro_state.hot_count + clock_pro_state.cold_target < clock_pro_state.capacity;
+    let frame = match frame_pool.pop() {
+        Some(frame) => frame,
+        None => clock_pro_state.run_hand_cold(page_table),
+    };
+    load_page(page_table, page_number, frame, current_timestamp);
+
+    if in_test {
+        clock_pro_state.cold_target =
+            std::cmp::min(clock_pro_state.cold_target + 1, clock_pro_state.capacity);
+    }
+    let is_hot = in_test || warming_up;
+    clock_pro_state.insert_at_head(ClockProEntry {
+        page_number,
+        is_hot,
+        is_resident: true,
+        is_referenced: false,
+        in_test: !is_hot,
+    });
+    if is_hot {
+        clock_pro_state.hot_count += 1;
+        clock_pro_state.balance_hot_pages();
+    } else {
+        clock_pro_state.cold_count += 1;
+    }
+
+    frame
+}
+
+pub fn count_page_faults_clock_pro(
+    page_table: &mut [PTE],
+    page_references: Vec<i32>,
+    frame_pool: &mut Vec<i32>,
+) -> i32 {
+    let mut clock_pro_state = ClockProState::new(page_table, frame_pool);
+    let mut faults: i32 = 0;
+    let start_timestamp = first_timestamp(page_table);
+
+    for (current_timestamp, reference) in (start_timestamp..).zip(page_references) {
+        let page_number = reference as usize;
+        if !page_table[page_number].is_valid {
+            faults += 1;
+        }
+        process_page_access_clock_pro(
+            &mut clock_pro_state,
+            page_table,
+            page_number,
+            frame_pool,
+            current_timestamp,
+        );
+    }
+
+    faults
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AgingConfig {
+    pub counter_bits: u32,
+    pub tick_interval: usize,
+}
+
+impl Default for AgingConfig {
+    fn default() -> Self {
+        AgingConfig {
+            counter_bits: 8,
+            tick_interval: 1,
+        }
+    }
+}
+
+// `counters` and `referenced` are indexed by page number. Every `tick_interval`
+// accesses each resident page's counter is shifted right with its reference
+// bit entering at the top, so old references decay instead of accumulating.
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
+pub struct AgingState {
+    pub config: AgingConfig,
+    pub counters: Vec<u64>,
+    pub referenced: Vec<bool>,
+    pub accesses_since_tick: usize,
+}
+
+impl AgingState {
+    pub fn new(page_table: &[PTE], config: AgingConfig) -> Self {
+        AgingState {
+            config: AgingConfig {
+                counter_bits: config.counter_bits.clamp(1, 64),
+                tick_interval: std::cmp::max(config.tick_interval, 1),
+            },
+            counters: vec![0; page_table.len()],
+            referenced: vec![false; page_table.len()],
+            accesses_since_tick: 0,
+        }
+    }
+
+    pub fn tick(&mut self, page_table: &[PTE]) {
+        let top_bit = 1u64 << (self.config.counter_bits - 1);
+        for (page_number, pte) in page_table.iter().enumerate() {
+            if pte.is_valid {
+                let mut counter = self.counters[page_number] >> 1;
+                if self.referenced[page_number] {
+                    counter |= top_bit;
+                }
+                self.counters[page_number] = counter;
+                self.referenced[page_number] = false;
+            }
+        }
+        self.accesses_since_tick = 0;
+    }
+}
+
+pub fn process_page_access_aging(
+    aging_state: &mut AgingState,
+    page_table: &mut [PTE],
+    page_number: usize,
+    frame_pool: &mut Vec<i32>,
+    current_timestamp: i32,
+) -> i32 {
+    let mut frame: i32 = -1;
+
+    if page_table[page_number].is_valid {
+        page_table[page_number].last_access_timestamp = current_timestamp;
+        page_table[page_number].reference_count += 1;
+        frame = page_table[page_number].frame_number;
+    } else if let Some(free_frame) = frame_pool.pop() {
+        frame = free_frame;
+        load_page(page_table, page_number, frame, current_timestamp);
+        aging_state.counters[page_number] = 0;
+    } else {
+        let victim = (0..page_table.len())
+            .filter(|&i| page_table[i].is_valid)
+            .min_by_key(|&i| {
+                (
+                    aging_state.counters[i],
+                    aging_state.referenced[i],
+                    page_table[i].arrival_timestamp,
+                )
+            });
+        if let Some(position) = victim {
+            frame = page_table[position].frame_number;
+            aging_state.counters[position] = 0;
+            aging_state.referenced[position] = false;
+            replace_page(
+                position as i32,
+                page_table,
+                page_number,
+                frame,
+                current_timestamp,
+            );
+            aging_state.counters[page_number] = 0;
+        }
+    }
+
+    if frame > -1 {
+        aging_state.referenced[page_number] = true;
+    }
+    aging_state.accesses_since_tick += 1;
+    if aging_state.accesses_since_tick >= aging_state.config.tick_interval {
+        aging_state.tick(page_table);
+    }
+
+    frame
+}
+
+pub fn count_page_faults_aging(
+    page_table: &mut [PTE],
+    page_references: Vec<i32>,
+    frame_pool: &mut Vec<i32>,
+    config: AgingConfig,
+) -> i32 {
+    let mut aging_state = AgingState::new(page_table, config);
+    let mut faults: i32 = 0;
+    let start_timestamp = first_timestamp(page_table);
+
+    for (current_timestamp, reference) in (start_timestamp..).zip(page_references) {
+        let page_number = reference as usize;
+        if !page_table[page_number].is_valid {
+            faults += 1;
+        }
+        process_page_access_aging(
+            &mut aging_state,
+            page_table,
+            page_number,
+            frame_pool,
+            current_timestamp,
+        );
+    }
+
+    faults
+}
+
+// SplitMix64; small, fast and reproducible across platforms for a given seed.
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
+pub struct SeededRng {
+    pub state: u64,
+}
+
+impl SeededRng {
+    pub fn new(seed: u64) -> Self {
+        SeededRng { state: seed }
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    // Uniform in `0..bound` using Lemire's multiply-shift with rejection, so
+    // there is no modulo bias. Panics if `bound` is zero.
+    pub fn next_below(&mut self, bound: usize) -> usize {
+        assert!(bound > 0, "next_below needs a non-zero bound");
+        let bound = bound as u64;
+        let threshold = bound.wrapping_neg() % bound;
+        loop {
+            let product = self.next_u64() as u128 * bound as u128;
+            if product as u64 >= threshold {
+                return (product >> 64) as usize;
+            }
+        }
+    }
+
+    pub fn next_f64(&mut self) -> f64 {
+        (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64
+    }
+}
+
+pub fn process_page_access_random(
+    rng: &mut SeededRng,
+    page_table: &mut [PTE],
+    page_number: usize,
+    frame_pool: &mut Vec<i32>,
+    current_timestamp: i32,
+) -> i32 {
+    let mut frame: i32 = -1;
+
+    if page_table[page_number].is_valid {
+        page_table[page_number].last_access_timestamp = current_timestamp;
+        page_table[page_number].reference_count += 1;
+        frame = page_table[page_number].frame_number;
+    } else if let Some(free_frame) = frame_pool.pop() {
+        frame = free_frame;
+        load_page(page_table, page_number, frame, current_timestamp);
+    } else {
+        let resident: Vec<usize> = (0..page_table.len())
+            .filter(|&i| page_table[i].is_valid)
+            .collect();
+        if !resident.is_empty() {
+            let position = resident[rng.next_below(resident.len())];
+            frame = page_table[position].frame_number;
+            replace_page(
+                position as i32,
+                page_table,
+                page_number,
+                frame,
+                current_timestamp,
+            );
+        }
+    }
+
+    frame
+}
+
+pub fn count_page_faults_random(
+    page_table: &mut [PTE],
+    page_references: Vec<i32>,
+    frame_pool: &mut Vec<i32>,
+    seed: u64,
+) -> i32 {
+    let mut rng = SeededRng::new(seed);
+    let mut faults: i32 = 0;
+    let start_timestamp = first_timestamp(page_table);
+
+    for (current_timestamp, reference) in (start_timestamp..).zip(page_references) {
+        let page_number = reference as usize;
+        if !page_table[page_number].is_valid {
+            faults += 1;
+        }
+        process_page_access_random(
+            &mut rng,
+            page_table,
+            page_number,
+            frame_pool,
+            current_timestamp,
+        );
+    }
+
+    faults
+}
+
+pub fn process_page_access_mru(
+    page_table: &mut [PTE],
+    page_number: usize,
+    frame_pool: &mut Vec<i32>,
+    current_timestamp: i32,
+) -> i32 {
+    let mut frame: i32 = -1;
+
+    if page_table[page_number].is_valid {
+        page_table[page_number].last_access_timestamp = current_timestamp;
+        page_table[page_number].reference_count += 1;
+        frame = page_table[page_number].frame_number;
+    } else if let Some(free_frame) = frame_pool.pop() {
+        frame = free_frame;
+        load_page(page_table, page_number, frame, current_timestamp);
+    } else {
+        let mut largest_last_access_timestamp = i32::MIN;
+        let mut position: i32 = -1;
+        for (i, pte) in page_table.iter().enumerate() {
+            if pte.is_valid && pte.last_access_timestamp > largest_last_access_timestamp {
+                largest_last_access_timestamp = pte.last_access_timestamp;
+                position = i as i32;
+            }
+        }
+
+        if position > -1 {
+            frame = page_table[position as usize].frame_number;
+            replace_page(position, page_table, page_number, frame, current_timestamp);
+        }
+    }
+
+    frame
+}
+
+pub fn count_page_faults_mru(
+    page_table: &mut [PTE],
+    page_references: Vec<i32>,
+    frame_pool: &mut Vec<i32>,
+) -> i32 {
+    let mut faults: i32 = 0;
+    let start_timestamp = first_timestamp(page_table);
+
+    for (current_timestamp, reference) in (start_timestamp..).zip(page_references) {
+        let page_number = reference as usize;
+        if !page_table[page_number].is_valid {
+            faults += 1;
+        }
+        process_page_access_mru(page_table, page_number, frame_pool, current_timestamp);
+    }
+
+    faults
+}
+
+// Belady's optimal (MIN) policy: evicts the resident page whose next use lies
+// farthest in the future. It needs the whole reference string up front, so it
+// has no per-access form. Returns whether each reference faulted.
+pub fn process_references_opt(
+    page_table: &mut [PTE],
+    page_references: &[i32],
+    frame_pool: &mut Vec<i32>,
+) -> Vec<bool> {
+    let mut next_use = vec![usize::MAX; page_references.len()];
+    let mut upcoming: HashMap<i32, usize> = HashMap::new();
+    for (position, &page_number) in page_references.iter().enumerate().rev() {
+        if let Some(&next) = upcoming.get(&page_number) {
+            next_use[position] = next;
+        }
+        upcoming.insert(page_number, position);
+    }
+
+    let mut resident: BTreeSet<(usize, usize)> = BTreeSet::new();
+    let mut resident_next_use: HashMap<usize, usize> = HashMap::new();
+    for (page_number, pte) in page_table.iter().enumerate() {
+        if pte.is_valid {
+            let next = upcoming
+                .get(&(page_number as i32))
+                .copied()
+                .unwrap_or(usize::MAX);
+            resident.insert((next, page_number));
+            resident_next_use.insert(page_number, next);
+        }
+    }
+
+    let start_timestamp = first_timestamp(page_table);
+    let mut faults = Vec::with_capacity(page_references.len());
+    for (position, current_timestamp) in (0..page_references.len()).zip(start_timestamp..) {
+        let page_number = page_references[position] as usize;
+        if let Some(next) = resident_next_use.remove(&page_number) {
+            resident.remove(&(next, page_number));
+            page_table[page_number].last_access_timestamp = current_timestamp;
+            page_table[page_number].reference_count += 1;
+            faults.push(false);
+        } else {
+            let frame = match frame_pool.pop() {
+                Some(frame) => frame,
+                None => {
+                    let victim = match resident.iter().next_back() {
+                        Some(&entry) => entry,
+                        None => {
+                            faults.push(true);
+                            continue;
+                        }
+                    };
+                    resident.remove(&victim);
+                    resident_next_use.remove(&victim.1);
+                    evict_page(page_table, victim.1)
+                }
+            };
+            load_page(page_table, page_number, frame, current_timestamp);
+            faults.push(true);
+        }
+        resident.insert((next_use[position], page_number));
+        resident_next_use.insert(page_number, next_use[position]);
+    }
+
+    faults
+}
+
+pub fn count_page_faults_opt(
+    page_table: &mut [PTE],
+    page_references: Vec<i32>,
+    frame_pool: &mut Vec<i32>,
+) -> i32 {
+    process_references_opt(page_table, &page_references, frame_pool)
+        .iter()
+        .filter(|&&fault| fault)
+        .count() as i32
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReplacementPolicy {
+    Fifo,
+    Lru,
+    Lfu,
+    Mru,
+    Random(u64),
+    Clock,
+    ClockPro,
+    Aging(AgingConfig),
+    Arc,
+    Lirs,
+    TwoQ,
+}
+
+impl ReplacementPolicy {
+    pub fn name(&self) -> &'static str {
+        match self {
+            ReplacementPolicy::Fifo => "FIFO",
+            ReplacementPolicy::Lru => "LRU",
+            ReplacementPolicy::Lfu => "LFU",
+            ReplacementPolicy::Mru => "MRU",
+            ReplacementPolicy::Random(_) => "Random",
+            ReplacementPolicy::Clock => "CLOCK",
+            ReplacementPolicy::ClockPro => "CLOCK-Pro",
+            ReplacementPolicy::Aging(_) => "Aging",
+            ReplacementPolicy::Arc => "ARC",
+            ReplacementPolicy::Lirs => "LIRS",
+            ReplacementPolicy::TwoQ => "2Q",
+        }
+    }
+
+    pub fn count_page_faults(
+        &self,
+        page_table: &mut Vec<PTE>,
+        page_references: Vec<i32>,
+        frame_pool: &mut Vec<i32>,
+    ) -> i32 {
+        match *self {
+            ReplacementPolicy::Fifo => {
+                count_page_faults_fifo(page_table, page_references, frame_pool)
+            }
+            ReplacementPolicy::Lru => {
+                count_page_faults_lru(page_table, page_references, frame_pool)
+            }
+            ReplacementPolicy::Lfu => {
+                count_page_faults_lfu(page_table, page_references, frame_pool)
+            }
+            ReplacementPolicy::Mru => {
+                count_page_faults_mru(page_table, page_references, frame_pool)
+            }
+            ReplacementPolicy::Random(seed) => {
+                count_page_faults_random(page_table, page_references, frame_pool, seed)
+            }
+            ReplacementPolicy::Clock => {
+                count_page_faults_clock(page_table, page_references, frame_pool)
+            }
+            ReplacementPolicy::ClockPro => {
+                count_page_faults_clock_pro(page_table, page_references, frame_pool)
+            }
+            ReplacementPolicy::Aging(config) => {
+                count_page_faults_aging(page_table, page_references, frame_pool, config)
+            }
+            ReplacementPolicy::Arc => {
+                count_page_faults_arc(page_table, page_references, frame_pool)
+            }
+            ReplacementPolicy::Lirs => {
+                count_page_faults_lirs(page_table, page_references, frame_pool)
+            }
+            ReplacementPolicy::TwoQ => {
+                count_page_faults_2q(page_table, page_references, frame_pool)
+            }
+        }
+    }
+
+    // Runs the policy from an empty page table large enough for every page in
+    // the reference string and a frame pool of `frames` free frames.
+    pub fn count_page_faults_for_frames(&self, page_references: &[i32], frames: usize) -> i32 {
+        let page_count = page_references
+            .iter()
+            .map(|&p| p as usize + 1)
+            .max()
+            .unwrap_or(0);
+        let mut page_table: Vec<PTE> = vec![PTE::default(); page_count];
+        let mut frame_pool: Vec<i32> = (0..frames as i32).rev().collect();
+        self.count_page_faults(&mut page_table, page_references.to_vec(), &mut frame_pool)
+    }
+}
+
+// The policies `select_victim` can decide from PTE fields alone.
+pub fn selects_from_pte_fields(policy: ReplacementPolicy) -> bool {
+    matches!(
+        policy,
+        ReplacementPolicy::Fifo
+            | ReplacementPolicy::Lru
+            | ReplacementPolicy::Lfu
+            | ReplacementPolicy::Mru
+    )
+}
+
+// Picks the index of the victim among resident `candidates` for the policies
+// whose decision depends only on PTE fields; None for the stateful policies.
+pub fn select_victim(policy: ReplacementPolicy, candidates: &[PTE]) -> Option<usize> {
+    let resident = (0..candidates.len()).filter(|&i| candidates[i].is_valid);
+    match policy {
+        ReplacementPolicy::Fifo => resident.min_by_key(|&i| candidates[i].arrival_timestamp),
+        ReplacementPolicy::Lru => resident.min_by_key(|&i| candidates[i].last_access_timestamp),
+        ReplacementPolicy::Lfu => resident.min_by_key(|&i| {
+            (
+                candidates[i].reference_count,
+                candidates[i].arrival_timestamp,
+            )
+        }),
+        // `max_by_key` keeps the last maximum; take the first, as
+        // `process_page_access_mru` does.
+        ReplacementPolicy::Mru => {
+            resident.min_by_key(|&i| std::cmp::Reverse(candidates[i].last_access_timestamp))
+        }
+        _ => None,
+    }
+}
+
+// Per-access counterpart of `ReplacementPolicy`: carries whatever state the
+// policy keeps beyond the PTE fields between calls.
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
+pub enum PolicyState {
+    Fifo,
+    Lru,
+    Lfu,
+    Mru,
+    Random(SeededRng),
+    Clock(ClockState),
+    ClockPro(ClockProState),
+    Aging(AgingState),
+    Arc(ArcState),
+    Lirs(LirsState),
+    TwoQ(TwoQState),
+}
+
+impl PolicyState {
+    pub fn new(policy: ReplacementPolicy, page_table: &[PTE], frame_pool: &[i32]) -> Self {
+        match policy {
+            ReplacementPolicy::Fifo => PolicyState::Fifo,
+            ReplacementPolicy::Lru => PolicyState::Lru,
+            ReplacementPolicy::Lfu => PolicyState::Lfu,
+            ReplacementPolicy::Mru => PolicyState::Mru,
+            ReplacementPolicy::Random(seed) => PolicyState::Random(SeededRng::new(seed)),
+            ReplacementPolicy::Clock => PolicyState::Clock(ClockState::new(page_table)),
+            ReplacementPolicy::ClockPro => {
+                PolicyState::ClockPro(ClockProState::new(page_table, frame_pool))
+            }
+            ReplacementPolicy::Aging(config) => {
+                PolicyState::Aging(AgingState::new(page_table, config))
+            }
+            ReplacementPolicy::Arc => PolicyState::Arc(ArcState::new(page_table, frame_pool)),
+            ReplacementPolicy::Lirs => PolicyState::Lirs(LirsState::new(page_table, frame_pool)),
+            ReplacementPolicy::TwoQ => PolicyState::TwoQ(TwoQState::new(page_table, frame_pool)),
+        }
+    }
+
+    pub fn process_page_access(
+        &mut self,
+        page_table: &mut Vec<PTE>,
+        page_number: usize,
+        frame_pool: &mut Vec<i32>,
+        current_timestamp: i32,
+    ) -> i32 {
+        match self {
+            PolicyState::Fifo => {
+                process_page_access_fifo(page_table, page_number, frame_pool, current_timestamp)
+            }
+            PolicyState::Lru => {
+                process_page_access_lru(page_table, page_number, frame_pool, current_timestamp)
+            }
+            PolicyState::Lfu => {
+                process_page_access_lfu(page_table, page_number, frame_pool, current_timestamp)
+            }
+            PolicyState::Mru => {
+                process_page_access_mru(page_table, page_number, frame_pool, current_timestamp)
+            }
+            PolicyState::Random(rng) => process_page_access_random(
+                rng,
+                page_table,
+                page_number,
+                frame_pool,
+                current_timestamp,
+            ),
+            PolicyState::Clock(clock_state) => process_page_access_clock(
+                clock_state,
+                page_table,
+                page_number,
+                frame_pool,
+                current_timestamp,
+            ),
+            PolicyState::ClockPro(clock_pro_state) => process_page_access_clock_pro(
+                clock_pro_state,
+                page_table,
+                page_number,
+                frame_pool,
+                current_timestamp,
+            ),
+            PolicyState::Aging(aging_state) => process_page_access_aging(
+                aging_state,
+                page_table,
+                page_number,
+                frame_pool,
+                current_timestamp,
+            ),
+            PolicyState::Arc(arc_state) => process_page_access_arc(
+                arc_state,
+                page_table,
+                page_number,
+                frame_pool,
+                current_timestamp,
+            ),
+            PolicyState::Lirs(lirs_state) => process_page_access_lirs(
+                lirs_state,
+                page_table,
+                page_number,
+                frame_pool,
+                current_timestamp,
+            ),
+            PolicyState::TwoQ(two_q_state) => process_page_access_2q(
+                two_q_state,
+                page_table,
+                page_number,
+                frame_pool,
+                current_timestamp,
+            ),
+        }
+    }
+}
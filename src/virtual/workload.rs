@@ -0,0 +1,234 @@
+use crate::r#virtual::r#virtual::SeededRng;
+use serde::{Deserialize, Serialize};
+
+// Reference string models. Every generator is driven by a `SeededRng`, so a
+// workload and a seed always produce the same string.
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
+pub enum Workload {
+    // Every page equally likely.
+    Uniform {
+        page_count: usize,
+    },
+    // Page k is referenced with probability proportional to 1 / (k + 1)^exponent.
+    Zipf {
+        page_count: usize,
+        exponent: f64,
+    },
+    // 0, 1, ..., page_count - 1, then wraps around.
+    Scan {
+        page_count: usize,
+    },
+    // Repeats start, start + 1, ..., start + loop_length - 1.
+    Loop {
+        start: usize,
+        loop_length: usize,
+    },
+    // Each phase picks a window of `working_set_size` consecutive pages at a
+    // random offset; a reference falls inside it with probability `locality`
+    // and anywhere in `page_count` otherwise.
+    PhaseChange {
+        page_count: usize,
+        working_set_size: usize,
+        phase_length: usize,
+        locality: f64,
+    },
+    // Each reference comes from one component, chosen by weight; components
+    // keep their own position between picks.
+    Mixed {
+        components: Vec<(Workload, f64)>,
+    },
+}
+
+impl Workload {
+    // Size the page table needs to hold every page the workload references.
+    pub fn page_count(&self) -> usize {
+        match self {
+            Workload::Uniform { page_count }
+            | Workload::Zipf { page_count, .. }
+            | Workload::Scan { page_count }
+            | Workload::PhaseChange { page_count, .. } => *page_count,
+            Workload::Loop { start, loop_length } => start + loop_length,
+            Workload::Mixed { components } => components
+                .iter()
+                .map(|(workload, _)| workload.page_count())
+                .max()
+                .unwrap_or(0),
+        }
+    }
+
+    // Panics on a workload that could not produce a reference: no pages, an
+    // empty loop, window or phase, or a mix with no positive weight.
+    pub fn validate(&self) {
+        match self {
+            Workload::Uniform { page_count }
+            | Workload::Zipf { page_count, .. }
+            | Workload::Scan { page_count } => {
+                assert!(*page_count > 0, "workload needs a non-zero page_count")
+            }
+            Workload::Loop { loop_length, .. } => {
+                assert!(
+                    *loop_length > 0,
+                    "loop workload needs a non-zero loop_length"
+                )
+            }
+            Workload::PhaseChange {
+                page_count,
+                working_set_size,
+                phase_length,
+                ..
+            } => {
+                assert!(*page_count > 0, "workload needs a non-zero page_count");
+                assert!(
+                    *working_set_size > 0,
+                    "phase change workload needs a non-zero working_set_size"
+                );
+                assert!(
+                    *phase_length > 0,
+                    "phase change workload needs a non-zero phase_length"
+                );
+            }
+            Workload::Mixed { components } => {
+                assert!(
+                    !components.is_empty(),
+                    "mixed workload needs at least one component"
+                );
+                assert!(
+                    components
+                        .iter()
+                        .all(|(_, weight)| weight.is_finite() && *weight >= 0.0),
+                    "mixed workload weights must be finite and non-negative"
+                );
+                assert!(
+                    components.iter().any(|(_, weight)| *weight > 0.0),
+                    "mixed workload needs a positive weight"
+                );
+                for (component, _) in components.iter() {
+                    component.validate();
+                }
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum GeneratorState {
+    Stateless,
+    Zipf { cdf: Vec<f64> },
+    Phase { base: usize, remaining: usize },
+    Mixed { children: Vec<WorkloadGenerator> },
+}
+
+// Endless iterator over the references of a workload.
+#[derive(Debug, Clone, PartialEq)]
+pub struct WorkloadGenerator {
+    pub workload: Workload,
+    rng: SeededRng,
+    position: usize,
+    state: GeneratorState,
+}
+
+impl WorkloadGenerator {
+    pub fn new(workload: Workload, seed: u64) -> Self {
+        workload.validate();
+        let mut rng = SeededRng::new(seed);
+        let state = match &workload {
+            Workload::Zipf {
+                page_count,
+                exponent,
+            } => {
+                let mut total = 0.0;
+                let mut cdf: Vec<f64> = (0..*page_count)
+                    .map(|k| {
+                        total += 1.0 / ((k + 1) as f64).powf(*exponent);
+                        total
+                    })
+                    .collect();
+                for value in cdf.iter_mut() {
+                    *value /= total;
+                }
+                GeneratorState::Zipf { cdf }
+            }
+            Workload::PhaseChange { .. } => GeneratorState::Phase {
+                base: 0,
+                remaining: 0,
+            },
+            Workload::Mixed { components } => GeneratorState::Mixed {
+                children: components
+                    .iter()
+                    .map(|(component, _)| WorkloadGenerator::new(component.clone(), rng.next_u64()))
+                    .collect(),
+            },
+            _ => GeneratorState::Stateless,
+        };
+        WorkloadGenerator {
+            workload,
+            rng,
+            position: 0,
+            state,
+        }
+    }
+
+    fn next_page(&mut self) -> usize {
+        let position = self.position;
+        self.position += 1;
+        match (&self.workload, &mut self.state) {
+            (Workload::Uniform { page_count }, _) => self.rng.next_below(*page_count),
+            (Workload::Zipf { .. }, GeneratorState::Zipf { cdf }) => {
+                let sample = self.rng.next_f64();
+                std::cmp::min(cdf.partition_point(|&value| value < sample), cdf.len() - 1)
+            }
+            (Workload::Scan { page_count }, _) => position % page_count,
+            (Workload::Loop { start, loop_length }, _) => start + position % loop_length,
+            (
+                Workload::PhaseChange {
+                    page_count,
+                    working_set_size,
+                    phase_length,
+                    locality,
+                },
+                GeneratorState::Phase { base, remaining },
+            ) => {
+                let window = std::cmp::min(*working_set_size, *page_count);
+                if *remaining == 0 {
+                    *base = self.rng.next_below(page_count - window + 1);
+                    *remaining = *phase_length;
+                }
+                *remaining -= 1;
+                if self.rng.next_f64() < *locality {
+                    *base + self.rng.next_below(window)
+                } else {
+                    self.rng.next_below(*page_count)
+                }
+            }
+            (Workload::Mixed { components }, GeneratorState::Mixed { children }) => {
+                let total: f64 = components.iter().map(|(_, weight)| weight).sum();
+                let mut sample = self.rng.next_f64() * total;
+                let mut chosen = children.len() - 1;
+                for (index, (_, weight)) in components.iter().enumerate() {
+                    if sample < *weight {
+                        chosen = index;
+                        break;
+                    }
+                    sample -= weight;
+                }
+                children[chosen].next_page()
+            }
+            _ => unreachable!("generator state always matches its workload"),
+        }
+    }
+}
+
+impl Iterator for WorkloadGenerator {
+    type Item = i32;
+
+    fn next(&mut self) -> Option<i32> {
+        Some(self.next_page() as i32)
+    }
+}
+
+// A reference string of `length` references, ready for `count_page_faults_*`.
+pub fn generate_workload(workload: &Workload, length: usize, seed: u64) -> Vec<i32> {
+    WorkloadGenerator::new(workload.clone(), seed)
+        .take(length)
+        .collect()
+}